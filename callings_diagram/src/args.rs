@@ -0,0 +1,112 @@
+use lcr_core::Date;
+
+pub const USAGE: &str = "usage: callings-diagram [--strict] [--format <drawio|svg|html>] [--seed <number>] [--as-of <YYYY-MM-DD>] [--page-per-org] [--filter <preset>] <input file> <output file>";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    Drawio,
+    Svg,
+    Html,
+}
+
+impl OutputFormat {
+    fn from_name(name: &str) -> Option<OutputFormat> {
+        match name.to_lowercase().as_str() {
+            "drawio" => Some(OutputFormat::Drawio),
+            "svg" => Some(OutputFormat::Svg),
+            "html" => Some(OutputFormat::Html),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct Args {
+    pub input_file: String,
+    pub output_file: String,
+    pub strict: bool,
+    pub format: OutputFormat,
+    pub seed: Option<u64>,
+    pub as_of: Date,
+    pub page_per_org: bool,
+    pub filter: Option<String>,
+}
+
+impl Args {
+    pub fn parse(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+        let mut strict = false;
+        let mut format = None;
+        let mut seed = None;
+        let mut as_of = None;
+        let mut page_per_org = false;
+        let mut filter = None;
+        let mut paths = Vec::new();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--strict" => strict = true,
+                "--page-per-org" => page_per_org = true,
+                "--format" => {
+                    let name = args.next().ok_or("--format requires a format name")?;
+                    format = Some(
+                        OutputFormat::from_name(&name)
+                            .ok_or_else(|| format!("unrecognized format '{}'", name))?,
+                    );
+                }
+                "--seed" => {
+                    let number = args.next().ok_or("--seed requires a number")?;
+                    seed = Some(
+                        number
+                            .parse::<u64>()
+                            .map_err(|_| format!("invalid --seed number '{}'", number))?,
+                    );
+                }
+                "--as-of" => {
+                    let date = args.next().ok_or("--as-of requires a date")?;
+                    as_of = Some(
+                        date.parse::<Date>()
+                            .map_err(|err| format!("invalid --as-of date: {}", err))?,
+                    );
+                }
+                "--filter" => {
+                    filter = Some(args.next().ok_or("--filter requires a preset name")?);
+                }
+                flag if flag.starts_with("--") => {
+                    return Err(format!("unrecognized option '{}'", flag));
+                }
+                _ => paths.push(arg),
+            }
+        }
+
+        if paths.len() != 2 {
+            return Err("expected an input file and an output file".to_string());
+        }
+
+        let output_file = paths.pop().unwrap_or_default();
+        let input_file = paths.pop().unwrap_or_default();
+
+        // Without --format, go by the output file's extension
+        let format = format.unwrap_or_else(|| {
+            let output_file = output_file.to_lowercase();
+
+            if output_file.ends_with(".svg") {
+                OutputFormat::Svg
+            } else if output_file.ends_with(".html") || output_file.ends_with(".htm") {
+                OutputFormat::Html
+            } else {
+                OutputFormat::Drawio
+            }
+        });
+
+        Ok(Args {
+            input_file,
+            output_file,
+            strict,
+            format,
+            seed,
+            as_of: as_of.unwrap_or_else(Date::today),
+            page_per_org,
+            filter,
+        })
+    }
+}
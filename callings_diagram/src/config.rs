@@ -1,3 +1,5 @@
+use crate::layout::Style;
+use lcr_core::OrderingRules;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -14,10 +16,247 @@ pub struct Dimensions {
     pub height: i32,
 }
 
+// What to do with an org in `org_ordering` that has nothing to draw
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OrgFallback {
+    // Leave it off the diagram
+    Skip,
+    // Draw the org bubble with nothing in it
+    Placeholder,
+    // Fail to generate the diagram
+    Error,
+}
+
+// How the orgs inside a sub-org (e.g. the individual Primary classes) are drawn
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GrandchildOrgs {
+    // As their own bubbles inside the sub-org's bubble
+    #[default]
+    Nested,
+    // Their callings go straight into the sub-org's bubble
+    Flattened,
+}
+
+// How a kind of bubble is drawn. Anything left out falls back to the next most general setting
+// (an org's own theme, then the diagram's theme, then the built-in look).
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct BubbleStyle {
+    pub fill_color: Option<String>,
+    pub stroke_color: Option<String>,
+    pub stroke_width: Option<i32>,
+    pub dashed: Option<bool>,
+    pub rounded: Option<bool>,
+    pub font_family: Option<String>,
+    pub font_color: Option<String>,
+    pub font_size: Option<i32>,
+    // Org names, and calling names on calling bubbles
+    pub title_font_size: Option<i32>,
+}
+
+impl BubbleStyle {
+    // Fills in anything not set here from `fallback`
+    pub fn or(&self, fallback: &BubbleStyle) -> BubbleStyle {
+        BubbleStyle {
+            fill_color: self.fill_color.clone().or(fallback.fill_color.clone()),
+            stroke_color: self.stroke_color.clone().or(fallback.stroke_color.clone()),
+            stroke_width: self.stroke_width.or(fallback.stroke_width),
+            dashed: self.dashed.or(fallback.dashed),
+            rounded: self.rounded.or(fallback.rounded),
+            font_family: self.font_family.clone().or(fallback.font_family.clone()),
+            font_color: self.font_color.clone().or(fallback.font_color.clone()),
+            font_size: self.font_size.or(fallback.font_size),
+            title_font_size: self.title_font_size.or(fallback.title_font_size),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Theme {
+    pub org: BubbleStyle,
+    // Falls back to `org`
+    pub sub_org: BubbleStyle,
+    pub calling: BubbleStyle,
+    // Falls back to `calling`
+    pub vacant_calling: BubbleStyle,
+}
+
+// The look of the diagram when nothing is configured. `org` and `calling` set everything, while
+// `sub_org` and `vacant_calling` only set what differs from them.
+pub struct BuiltInTheme {
+    pub org: Style,
+    pub sub_org: BubbleStyle,
+    pub calling: Style,
+    pub vacant_calling: BubbleStyle,
+}
+
+impl Theme {
+    pub fn built_in() -> BuiltInTheme {
+        BuiltInTheme {
+            org: Style {
+                fill_color: "#FFFFFF".to_string(),
+                stroke_color: "#000000".to_string(),
+                stroke_width: 4,
+                dashed: false,
+                rounded: true,
+                font_family: "Helvetica".to_string(),
+                font_color: "#000000".to_string(),
+                font_size: 16,
+                title_font_size: 22,
+            },
+            sub_org: BubbleStyle {
+                stroke_color: Some("#9E9E9E".to_string()),
+                font_color: Some("#6B6B6B".to_string()),
+                ..BubbleStyle::default()
+            },
+            calling: Style {
+                fill_color: "#FFFFFF".to_string(),
+                stroke_color: "#000000".to_string(),
+                stroke_width: 1,
+                dashed: false,
+                rounded: true,
+                font_family: "Helvetica".to_string(),
+                font_color: "#000000".to_string(),
+                font_size: 16,
+                title_font_size: 18,
+            },
+            vacant_calling: BubbleStyle {
+                fill_color: Some("#F5F5F5".to_string()),
+                stroke_color: Some("#9E9E9E".to_string()),
+                dashed: Some(true),
+                font_color: Some("#9E9E9E".to_string()),
+                ..BubbleStyle::default()
+            },
+        }
+    }
+
+    // Fills in anything not set here from `fallback`
+    pub fn or(&self, fallback: &Theme) -> Theme {
+        Theme {
+            org: self.org.or(&fallback.org),
+            sub_org: self.sub_org.or(&fallback.sub_org),
+            calling: self.calling.or(&fallback.calling),
+            vacant_calling: self.vacant_calling.or(&fallback.vacant_calling),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SetApartIndicator {
+    None,
+    // A "Not set apart" label in the corner of the calling bubble
+    Badge,
+    // The calling bubble's outline is colored
+    Border,
+    // A "!" in the corner of the calling bubble
+    Icon,
+}
+
+// How callings whose member hasn't been set apart yet are marked
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct NotSetApart {
+    pub indicator: SetApartIndicator,
+    pub color: String,
+    pub text_color: String,
+    // Once this many days have passed since the member was sustained, `overdue_color` is used
+    // instead of `color`
+    pub overdue_after_days: Option<u32>,
+    pub overdue_color: String,
+}
+
+impl Default for NotSetApart {
+    fn default() -> NotSetApart {
+        NotSetApart {
+            indicator: SetApartIndicator::Badge,
+            color: "#F57C00".to_string(),
+            text_color: "#FFFFFF".to_string(),
+            overdue_after_days: None,
+            overdue_color: "#D32F2F".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct TenureBucket {
+    // Callings held for at least this many months (and less than the next bucket's) get `color`
+    pub min_months: u32,
+    pub color: String,
+    pub label: String,
+}
+
+// Colors filled calling bubbles by how long the member has held the calling
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct TenureColors {
+    pub buckets: Vec<TenureBucket>,
+    pub show_legend: bool,
+}
+
+impl TenureColors {
+    pub fn bucket(&self, months: u32) -> Option<&TenureBucket> {
+        self.buckets
+            .iter()
+            .filter(|b| b.min_months <= months)
+            .max_by_key(|b| b.min_months)
+    }
+}
+
+impl Default for TenureColors {
+    fn default() -> TenureColors {
+        let bucket = |min_months, color: &str, label: &str| TenureBucket {
+            min_months,
+            color: color.to_string(),
+            label: label.to_string(),
+        };
+
+        TenureColors {
+            buckets: vec![
+                bucket(0, "#C8E6C9", "Under 1 year"),
+                bucket(12, "#FFF9C4", "1-3 years"),
+                bucket(36, "#FFE0B2", "3-5 years"),
+                bucket(60, "#FFCDD2", "5+ years"),
+            ],
+            show_legend: true,
+        }
+    }
+}
+
+// Draws callings that share a name within a bubble (e.g. a class's teachers) as one bubble once
+// there are at least `min_count` of them
+#[derive(Debug, Deserialize, Serialize)]
+#[serde(default)]
+pub struct CollapseRepeatedCallings {
+    pub min_count: usize,
+    // List who holds the callings, rather than only how many are filled
+    pub list_names: bool,
+}
+
+impl Default for CollapseRepeatedCallings {
+    fn default() -> CollapseRepeatedCallings {
+        CollapseRepeatedCallings {
+            min_count: 3,
+            list_names: true,
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize)]
 pub struct OrgOrdering {
     pub name: String,
+    #[serde(default)]
     pub begins_new_column: bool,
+    #[serde(default)]
+    pub show_vacancies: Option<bool>,
+    #[serde(default)]
+    pub grandchild_orgs: Option<GrandchildOrgs>,
+    // Overrides `Config::theme` for this org's bubbles
+    #[serde(default)]
+    pub theme: Theme,
 }
 
 #[derive(Debug, Deserialize, Serialize)]
@@ -33,5 +272,60 @@ pub struct Config {
     pub calling_bubble_vertical_margins: i32,
     pub calling_bubble_dimensions: Dimensions,
     pub calling_bubble_min_horizontal_margin: i32,
+    #[serde(default = "default_date_format")]
+    pub date_format: String,
+    // See `template::DEFAULT_CALLING_TEMPLATE`
+    #[serde(default = "default_calling_template")]
+    pub calling_template: String,
+    #[serde(default)]
+    pub show_vacancies: bool,
+    #[serde(default)]
+    pub hidden_vacant_callings: Vec<String>,
+    #[serde(default)]
+    pub grandchild_orgs: GrandchildOrgs,
+    // The order of the callings and sub-orgs inside each org bubble
+    #[serde(default)]
+    pub calling_order: OrderingRules,
+    // When set, orgs are spread over this many columns automatically instead of only breaking at
+    // `begins_new_column`
+    #[serde(default)]
+    pub column_count: Option<usize>,
+    // Columns on each org's page when generating a page per org
+    #[serde(default = "default_org_page_column_count")]
+    pub org_page_column_count: usize,
+    // Orgs with no callings to show
+    #[serde(default = "default_empty_orgs")]
+    pub empty_orgs: OrgFallback,
+    // Orgs that aren't in the input file at all
+    #[serde(default = "default_missing_orgs")]
+    pub missing_orgs: OrgFallback,
+    #[serde(default)]
+    pub theme: Theme,
+    #[serde(default)]
+    pub not_set_apart: NotSetApart,
+    #[serde(default)]
+    pub tenure_colors: Option<TenureColors>,
+    #[serde(default)]
+    pub collapse_repeated_callings: Option<CollapseRepeatedCallings>,
     pub org_ordering: Vec<OrgOrdering>,
 }
+
+fn default_date_format() -> String {
+    lcr_core::date::LONG_FORMAT.to_string()
+}
+
+fn default_org_page_column_count() -> usize {
+    2
+}
+
+fn default_calling_template() -> String {
+    crate::template::DEFAULT_CALLING_TEMPLATE.to_string()
+}
+
+fn default_empty_orgs() -> OrgFallback {
+    OrgFallback::Placeholder
+}
+
+fn default_missing_orgs() -> OrgFallback {
+    OrgFallback::Error
+}
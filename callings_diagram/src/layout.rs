@@ -0,0 +1,1549 @@
+use crate::config::{
+    BubbleStyle, CollapseRepeatedCallings, Config, GrandchildOrgs, OrgFallback, OrgOrdering,
+    SetApartIndicator, TenureColors, Theme,
+};
+use crate::template::{Template, TemplateError};
+use lcr_core::Date;
+use lcr_core::data::{Calling, Organization};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+#[derive(Debug)]
+pub enum LayoutError {
+    UnrecognizedOrg(String),
+    EmptyOrg(String),
+    InvalidTemplate(TemplateError),
+    BubbleTooNarrow { width: i32 },
+}
+
+impl std::error::Error for LayoutError {}
+
+impl fmt::Display for LayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LayoutError::UnrecognizedOrg(name) => write!(f, "Unrecognized org name: {}", name),
+            LayoutError::EmptyOrg(name) => write!(f, "Org has no callings to show: {}", name),
+            LayoutError::InvalidTemplate(err) => write!(f, "Invalid calling_template: {}", err),
+            LayoutError::BubbleTooNarrow { width } => write!(
+                f,
+                "Org bubble width too small: {} is not wide enough for a calling bubble",
+                width
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+impl Rect {
+    pub fn right(&self) -> i32 {
+        self.x + self.width
+    }
+
+    pub fn bottom(&self) -> i32 {
+        self.y + self.height
+    }
+}
+
+// Everything that gets drawn on the diagram, independent of the output format
+pub struct Scene {
+    pub id: String,
+    pub name: String,
+    // Each node comes before the nodes it contains
+    pub nodes: Vec<Node>,
+}
+
+impl Scene {
+    // The smallest rectangle containing every node
+    pub fn bounds(&self) -> Rect {
+        let mut nodes = self.nodes.iter().map(|n| n.absolute);
+
+        let Some(first) = nodes.next() else {
+            return Rect::default();
+        };
+
+        let (x, y, right, bottom) = nodes.fold(
+            (first.x, first.y, first.right(), first.bottom()),
+            |(x, y, right, bottom), r| {
+                (
+                    x.min(r.x),
+                    y.min(r.y),
+                    right.max(r.right()),
+                    bottom.max(r.bottom()),
+                )
+            },
+        );
+
+        Rect {
+            x,
+            y,
+            width: right - x,
+            height: bottom - y,
+        }
+    }
+}
+
+pub struct Node {
+    pub id: String,
+    pub parent_id: Option<String>,
+    // Position within the parent bubble (or the page for top-level org bubbles), the way draw.io
+    // positions cells inside a swimlane
+    pub relative: Rect,
+    // Position on the page
+    pub absolute: Rect,
+    pub kind: NodeKind,
+    pub style: Style,
+}
+
+// Text lines are this many times their font size apart
+pub const LINE_HEIGHT: f64 = 1.2;
+
+// Space kept between the text of a summary bubble and its top and bottom edges
+const SUMMARY_BUBBLE_PADDING: i32 = 10;
+
+// A `config::BubbleStyle` with every setting filled in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Style {
+    pub fill_color: String,
+    pub stroke_color: String,
+    pub stroke_width: i32,
+    pub dashed: bool,
+    pub rounded: bool,
+    pub font_family: String,
+    pub font_color: String,
+    pub font_size: i32,
+    pub title_font_size: i32,
+}
+
+impl Style {
+    // Fills in anything `style` leaves out from `base`
+    fn resolve(style: &BubbleStyle, base: &Style) -> Style {
+        Style {
+            fill_color: style.fill_color.clone().unwrap_or(base.fill_color.clone()),
+            stroke_color: style
+                .stroke_color
+                .clone()
+                .unwrap_or(base.stroke_color.clone()),
+            stroke_width: style.stroke_width.unwrap_or(base.stroke_width),
+            dashed: style.dashed.unwrap_or(base.dashed),
+            rounded: style.rounded.unwrap_or(base.rounded),
+            font_family: style
+                .font_family
+                .clone()
+                .unwrap_or(base.font_family.clone()),
+            font_color: style.font_color.clone().unwrap_or(base.font_color.clone()),
+            font_size: style.font_size.unwrap_or(base.font_size),
+            title_font_size: style.title_font_size.unwrap_or(base.title_font_size),
+        }
+    }
+
+    pub fn line_font_size(&self, line_style: TextStyle) -> i32 {
+        match line_style {
+            TextStyle::Title => self.title_font_size,
+            TextStyle::Body | TextStyle::Emphasis => self.font_size,
+        }
+    }
+}
+
+// A theme's style for each kind of bubble, with anything it leaves out taken from the built-in theme
+struct ThemeStyles {
+    org: Style,
+    sub_org: Style,
+    calling: Style,
+    vacant_calling: Style,
+}
+
+impl ThemeStyles {
+    fn resolve(theme: &Theme) -> ThemeStyles {
+        let built_in = Theme::built_in();
+
+        // Sub-org bubbles fall back to org bubbles, and vacant calling bubbles to calling bubbles,
+        // before the built-in look of those
+        ThemeStyles {
+            org: Style::resolve(&theme.org, &built_in.org),
+            sub_org: Style::resolve(
+                &theme.sub_org.or(&built_in.sub_org).or(&theme.org),
+                &built_in.org,
+            ),
+            calling: Style::resolve(&theme.calling, &built_in.calling),
+            vacant_calling: Style::resolve(
+                &theme
+                    .vacant_calling
+                    .or(&built_in.vacant_calling)
+                    .or(&theme.calling),
+                &built_in.calling,
+            ),
+        }
+    }
+}
+
+// A bubble listing the color of each tenure bucket
+fn tenure_legend(tenure_colors: &TenureColors, x: i32, y: i32, conf: &Config) -> Vec<Node> {
+    let styles = ThemeStyles::resolve(&conf.theme);
+    let entry_style = styles.calling;
+
+    let width = 300;
+    let margin = 20;
+    let entry_height = 40;
+
+    let mut buckets = tenure_colors.buckets.iter().collect::<Vec<_>>();
+    buckets.sort_by_key(|b| b.min_months);
+
+    let mut nodes = vec![Node::new(
+        "tenure-legend".to_string(),
+        None,
+        Rect {
+            x,
+            y,
+            width,
+            height: conf.org_bubble_title_height
+                + margin
+                + buckets.len() as i32 * (entry_height + margin),
+        },
+        NodeKind::Org {
+            name: "Time in Calling".to_string(),
+        },
+        styles.org,
+    )];
+
+    for (i, bucket) in buckets.into_iter().enumerate() {
+        nodes.push(Node::new(
+            format!("tenure-legend-{}", i),
+            Some("tenure-legend".to_string()),
+            Rect {
+                x: margin,
+                y: conf.org_bubble_title_height + margin + i as i32 * (entry_height + margin),
+                width: width - 2 * margin,
+                height: entry_height,
+            },
+            NodeKind::Calling {
+                lines: vec![TextLine::new(bucket.label.clone(), TextStyle::Body)],
+            },
+            Style {
+                fill_color: bucket.color.clone(),
+                ..entry_style.clone()
+            },
+        ));
+    }
+
+    nodes
+}
+
+// A top-level org bubble that has been laid out but not yet placed in a column
+struct OrgBubble<'a> {
+    name: &'a str,
+    begins_new_column: bool,
+    id: String,
+    style: Style,
+    contents: Vec<Node>,
+    height: i32,
+}
+
+// Settings that apply to everything inside a top-level org bubble
+struct OrgSettings<'a> {
+    show_vacancies: bool,
+    grandchild_orgs: GrandchildOrgs,
+    calling_template: &'a Template,
+    as_of: Date,
+    org_style: Style,
+    sub_org_style: Style,
+    calling_style: Style,
+    vacant_calling_style: Style,
+}
+
+impl<'a> OrgSettings<'a> {
+    fn new(
+        ordering: &OrgOrdering,
+        calling_template: &'a Template,
+        as_of: Date,
+        conf: &Config,
+    ) -> OrgSettings<'a> {
+        let styles = ThemeStyles::resolve(&ordering.theme.or(&conf.theme));
+
+        OrgSettings {
+            show_vacancies: ordering.show_vacancies.unwrap_or(conf.show_vacancies),
+            grandchild_orgs: ordering.grandchild_orgs.unwrap_or(conf.grandchild_orgs),
+            calling_template,
+            as_of,
+            org_style: styles.org,
+            sub_org_style: styles.sub_org,
+            calling_style: styles.calling,
+            vacant_calling_style: styles.vacant_calling,
+        }
+    }
+}
+
+pub enum NodeKind {
+    Org { name: String },
+    SubOrg { name: String },
+    Calling { lines: Vec<TextLine> },
+    // A small label in the corner of a calling bubble
+    Badge { text: String },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextStyle {
+    Title,
+    Body,
+    Emphasis,
+}
+
+// A line of text on a calling bubble. Empty lines are kept as spacing.
+pub struct TextLine {
+    pub text: String,
+    pub style: TextStyle,
+}
+
+impl TextLine {
+    fn new(text: impl Into<String>, style: TextStyle) -> TextLine {
+        TextLine {
+            text: text.into(),
+            style,
+        }
+    }
+}
+
+// How the ids of the diagram and its bubbles are chosen
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdScheme {
+    // Derived from the LCR ids (or names) of orgs and callings, so a bubble keeps the same id every
+    // time the diagram is generated as long as its org or calling still exists
+    Stable,
+    // Random-looking ids that are the same every time the same seed is used
+    Seeded(u64),
+}
+
+impl IdScheme {
+    // Each page of a multi-page diagram gets its own ids
+    fn for_page(self, page: usize) -> IdScheme {
+        match self {
+            IdScheme::Stable => IdScheme::Stable,
+            IdScheme::Seeded(seed) => IdScheme::Seeded(seed.wrapping_add(page as u64)),
+        }
+    }
+}
+
+// Lays out every org in `conf.org_ordering`
+pub fn layout(
+    orgs: &HashMap<String, Organization>,
+    conf: &Config,
+    id_scheme: IdScheme,
+    as_of: Date,
+) -> Result<Scene, LayoutError> {
+    let calling_template =
+        Template::parse(&conf.calling_template).map_err(LayoutError::InvalidTemplate)?;
+    let listed_orgs = listed_orgs(orgs, conf, &calling_template, as_of)?;
+
+    let bubbles = listed_orgs
+        .iter()
+        .map(|listed| PageBubble {
+            org: &listed.org,
+            settings: &listed.settings,
+            begins_new_column: listed.begins_new_column,
+            flatten: false,
+        })
+        .collect();
+
+    layout_page(
+        "Page-1",
+        None,
+        bubbles,
+        conf.column_count,
+        &mut IdGenerator::new(id_scheme),
+        conf,
+    )
+}
+
+// An overview page laid out like `layout`, followed by a page for each org. On an org's page, the
+// org's own callings and each of its sub-orgs get a full-width bubble.
+pub fn layout_pages(
+    orgs: &HashMap<String, Organization>,
+    conf: &Config,
+    id_scheme: IdScheme,
+    as_of: Date,
+) -> Result<Vec<Scene>, LayoutError> {
+    let calling_template =
+        Template::parse(&conf.calling_template).map_err(LayoutError::InvalidTemplate)?;
+    let listed_orgs = listed_orgs(orgs, conf, &calling_template, as_of)?;
+
+    let overview_bubbles = listed_orgs
+        .iter()
+        .map(|listed| PageBubble {
+            org: &listed.org,
+            settings: &listed.settings,
+            begins_new_column: listed.begins_new_column,
+            flatten: false,
+        })
+        .collect();
+
+    let mut pages = vec![layout_page(
+        "Overview",
+        None,
+        overview_bubbles,
+        conf.column_count,
+        &mut IdGenerator::new(id_scheme),
+        conf,
+    )?];
+
+    for (i, listed) in listed_orgs.iter().enumerate() {
+        let own_callings = Organization {
+            children: Vec::new(),
+            ..listed.org.as_ref().clone()
+        };
+        let show_vacancies = listed.settings.show_vacancies;
+        let flatten = listed.settings.grandchild_orgs == GrandchildOrgs::Flattened;
+
+        let mut bubbles = Vec::new();
+
+        for org in std::iter::once(&own_callings).chain(&listed.org.children) {
+            if has_shown_callings(org, show_vacancies, conf) {
+                bubbles.push(PageBubble {
+                    org,
+                    settings: &listed.settings,
+                    begins_new_column: false,
+                    flatten,
+                });
+            }
+        }
+
+        // An empty org still gets a page with its (empty) bubble on it
+        if bubbles.is_empty() {
+            bubbles.push(PageBubble {
+                org: &own_callings,
+                settings: &listed.settings,
+                begins_new_column: false,
+                flatten,
+            });
+        }
+
+        pages.push(layout_page(
+            &listed.org.name,
+            Some(&listed.org.name),
+            bubbles,
+            Some(conf.org_page_column_count),
+            &mut IdGenerator::new(id_scheme.for_page(i + 1)),
+            conf,
+        )?);
+    }
+
+    Ok(pages)
+}
+
+// An org from `conf.org_ordering` that is going on the diagram
+struct ListedOrg<'a> {
+    // A placeholder if the org isn't in the input file
+    org: Cow<'a, Organization>,
+    settings: OrgSettings<'a>,
+    begins_new_column: bool,
+}
+
+// Applies `conf.missing_orgs` and `conf.empty_orgs` to the orgs in `conf.org_ordering`
+fn listed_orgs<'a>(
+    orgs: &'a HashMap<String, Organization>,
+    conf: &'a Config,
+    calling_template: &'a Template,
+    as_of: Date,
+) -> Result<Vec<ListedOrg<'a>>, LayoutError> {
+    let mut listed_orgs = Vec::new();
+
+    for ordering in &conf.org_ordering {
+        let settings = OrgSettings::new(ordering, calling_template, as_of, conf);
+
+        let org = match orgs.get(&ordering.name) {
+            Some(org) => Cow::Borrowed(org),
+            None => match conf.missing_orgs {
+                OrgFallback::Skip => continue,
+                OrgFallback::Placeholder => Cow::Owned(empty_org(&ordering.name)),
+                OrgFallback::Error => {
+                    return Err(LayoutError::UnrecognizedOrg(ordering.name.clone()));
+                }
+            },
+        };
+
+        if !has_shown_callings(&org, settings.show_vacancies, conf) {
+            match conf.empty_orgs {
+                OrgFallback::Skip => continue,
+                OrgFallback::Placeholder => {}
+                OrgFallback::Error => return Err(LayoutError::EmptyOrg(ordering.name.clone())),
+            }
+        }
+
+        listed_orgs.push(ListedOrg {
+            org,
+            settings,
+            begins_new_column: ordering.begins_new_column,
+        });
+    }
+
+    Ok(listed_orgs)
+}
+
+// An org drawn as a top-level bubble on a page
+struct PageBubble<'a> {
+    org: &'a Organization,
+    settings: &'a OrgSettings<'a>,
+    begins_new_column: bool,
+    // Draw the callings of the org's sub-orgs in its own bubble, rather than nesting them
+    flatten: bool,
+}
+
+// `org_name` is the org the page is for, if it isn't the overview
+fn layout_page(
+    page_name: &str,
+    org_name: Option<&str>,
+    bubbles: Vec<PageBubble>,
+    column_count: Option<usize>,
+    ids: &mut IdGenerator,
+    conf: &Config,
+) -> Result<Scene, LayoutError> {
+    let mut nodes = Vec::new();
+    let scene_id = ids.scene_id(org_name);
+
+    // Org bubbles' heights are needed before they can be split into columns
+    let mut org_bubbles = Vec::new();
+
+    for bubble in bubbles {
+        let org_bubble_id = ids.org_bubble_id(bubble.org, None);
+
+        let (org_bubble_contents, org_bubble_height) = bubble_contents(
+            bubble.org,
+            &org_bubble_id,
+            conf.org_bubble_width,
+            bubble.flatten,
+            bubble.settings,
+            conf,
+            ids,
+        )?;
+
+        org_bubbles.push(OrgBubble {
+            name: &bubble.org.name,
+            begins_new_column: bubble.begins_new_column,
+            id: org_bubble_id,
+            style: bubble.settings.org_style.clone(),
+            contents: org_bubble_contents,
+            height: org_bubble_height,
+        });
+    }
+
+    let columns = match column_count {
+        Some(column_count) => {
+            let heights = org_bubbles
+                .iter()
+                .map(|b| conf.org_bubble_margins.top + b.height + conf.org_bubble_margins.bottom)
+                .collect::<Vec<_>>();
+            let breaks = org_bubbles
+                .iter()
+                .map(|b| b.begins_new_column)
+                .collect::<Vec<_>>();
+
+            balance_columns(&heights, &breaks, column_count)
+        }
+        None => org_bubbles
+            .iter()
+            .scan(0, |column, b| {
+                if b.begins_new_column {
+                    *column += 1;
+                }
+                Some(*column)
+            })
+            .collect(),
+    };
+
+    let column_width =
+        conf.org_bubble_width + conf.org_bubble_margins.right + conf.org_bubble_margins.left;
+    let mut org_bubble_cursor_y = conf.diagram_start_y;
+
+    for (i, org_bubble) in org_bubbles.into_iter().enumerate() {
+        if i > 0 && columns[i] != columns[i - 1] {
+            org_bubble_cursor_y = conf.diagram_start_y;
+        }
+
+        org_bubble_cursor_y += conf.org_bubble_margins.top;
+
+        nodes.push(Node::new(
+            org_bubble.id,
+            None,
+            Rect {
+                x: conf.diagram_start_x + columns[i] as i32 * column_width,
+                y: org_bubble_cursor_y,
+                width: conf.org_bubble_width,
+                height: org_bubble.height,
+            },
+            NodeKind::Org {
+                name: org_bubble.name.to_string(),
+            },
+            org_bubble.style,
+        ));
+        nodes.extend(org_bubble.contents);
+
+        org_bubble_cursor_y += org_bubble.height + conf.org_bubble_margins.bottom;
+    }
+
+    if let Some(tenure_colors) = conf.tenure_colors.as_ref().filter(|t| t.show_legend) {
+        // In a column of its own to the right of the org bubbles
+        let column = columns.iter().max().map_or(0, |c| c + 1);
+
+        nodes.extend(tenure_legend(
+            tenure_colors,
+            conf.diagram_start_x + column as i32 * column_width,
+            conf.diagram_start_y + conf.org_bubble_margins.top,
+            conf,
+        ));
+    }
+
+    resolve_absolute_positions(&mut nodes);
+
+    Ok(Scene {
+        id: scene_id,
+        name: page_name.to_string(),
+        nodes,
+    })
+}
+
+// Orgs in the input file that aren't listed in `conf.org_ordering`, and so aren't on the diagram
+pub fn unlisted_orgs<'a>(orgs: &'a HashMap<String, Organization>, conf: &Config) -> Vec<&'a str> {
+    let mut unlisted = orgs
+        .keys()
+        .filter(|name| !conf.org_ordering.iter().any(|o| &o.name == *name))
+        .map(|name| name.as_str())
+        .collect::<Vec<_>>();
+
+    unlisted.sort();
+    unlisted
+}
+
+fn empty_org(name: &str) -> Organization {
+    Organization {
+        id: None,
+        type_ids: Vec::new(),
+        name: name.to_string(),
+        display_sequence: None,
+        children: Vec::new(),
+        callings: Vec::new(),
+    }
+}
+
+// Splits orgs (in order) into `column_count` columns so that the tallest column is as short as
+// possible, returning the column of each org. Orgs marked to begin a new column always do, even if
+// that takes more than `column_count` columns.
+fn balance_columns(heights: &[i32], breaks: &[bool], column_count: usize) -> Vec<usize> {
+    let org_count = heights.len();
+
+    if org_count == 0 {
+        return Vec::new();
+    }
+
+    let manual_breaks = breaks.iter().skip(1).filter(|&&b| b).count();
+    let column_count = column_count.max(manual_breaks + 1).min(org_count);
+
+    let mut prefix_heights = vec![0; org_count + 1];
+    for (i, height) in heights.iter().enumerate() {
+        prefix_heights[i + 1] = prefix_heights[i] + height;
+    }
+
+    // tallest[c][i] is the shortest possible tallest column when the first `i` orgs are split
+    // into `c` columns, and start[c][i] is where the last of those columns begins
+    let mut tallest = vec![vec![i32::MAX; org_count + 1]; column_count + 1];
+    let mut start = vec![vec![0; org_count + 1]; column_count + 1];
+    tallest[0][0] = 0;
+
+    for c in 1..=column_count {
+        for i in c..=org_count {
+            // The column holds orgs `first..i`, and only its first org may begin a new column
+            for first in (c - 1..i).rev() {
+                if tallest[c - 1][first] != i32::MAX {
+                    let column_height = prefix_heights[i] - prefix_heights[first];
+                    let height = tallest[c - 1][first].max(column_height);
+
+                    if height < tallest[c][i] {
+                        tallest[c][i] = height;
+                        start[c][i] = first;
+                    }
+                }
+
+                if breaks[first] {
+                    break;
+                }
+            }
+        }
+    }
+
+    let mut columns = vec![0; org_count];
+    let mut end = org_count;
+
+    for c in (1..=column_count).rev() {
+        let first = start[c][end];
+        columns[first..end].fill(c - 1);
+        end = first;
+    }
+
+    columns
+}
+
+// Nodes come before the nodes they contain, so every parent has been positioned by the time its
+// children are reached
+fn resolve_absolute_positions(nodes: &mut [Node]) {
+    let mut positions: HashMap<String, (i32, i32)> = HashMap::new();
+
+    for node in nodes.iter_mut() {
+        let (parent_x, parent_y) = node
+            .parent_id
+            .as_ref()
+            .and_then(|id| positions.get(id).copied())
+            .unwrap_or((0, 0));
+
+        node.absolute = Rect {
+            x: parent_x + node.relative.x,
+            y: parent_y + node.relative.y,
+            ..node.relative
+        };
+        positions.insert(node.id.clone(), (node.absolute.x, node.absolute.y));
+    }
+}
+
+impl Node {
+    fn new(
+        id: String,
+        parent_id: Option<String>,
+        relative: Rect,
+        kind: NodeKind,
+        style: Style,
+    ) -> Node {
+        Node {
+            id,
+            parent_id,
+            relative,
+            absolute: relative,
+            kind,
+            style,
+        }
+    }
+}
+
+// Lays out the bubbles inside an org or sub-org bubble (its calling bubbles, followed by a nested
+// bubble for each child org that has callings to show) and the height the bubble needs to hold them.
+// If `flatten` is set, the callings of all of the org's descendants are drawn alongside its own
+// instead.
+fn bubble_contents(
+    org: &Organization,
+    bubble_id: &str,
+    bubble_width: i32,
+    flatten: bool,
+    settings: &OrgSettings,
+    conf: &Config,
+    ids: &mut IdGenerator,
+) -> Result<(Vec<Node>, i32), LayoutError> {
+    let min_bubble_height = conf.org_bubble_title_height
+        + conf.calling_bubble_dimensions.height
+        + 2 * conf.calling_bubble_vertical_margins;
+
+    let calling_bubbles_per_row = calling_bubbles_per_row(bubble_width, conf)?;
+    let calling_bubble_left_margin = (bubble_width
+        - conf.calling_bubble_dimensions.width * calling_bubbles_per_row)
+        / (calling_bubbles_per_row + 1);
+
+    let mut nodes = Vec::new();
+    let mut bubble_cursor_y = conf.org_bubble_title_height;
+
+    // Each calling is paired with the name of the org it's in
+    let callings: Vec<(&str, &Calling)> = if flatten {
+        org.callings_with_paths()
+            .into_iter()
+            .filter(|(_, c)| is_shown(c, settings.show_vacancies, conf))
+            .map(|(path, c)| (path.last().copied().unwrap_or(&org.name), c))
+            .collect()
+    } else {
+        shown_callings(org, settings.show_vacancies, conf)
+            .into_iter()
+            .map(|c| (org.name.as_str(), c))
+            .collect()
+    };
+
+    // Each calling bubble comes with its badge, if it has one. They're positioned once the height of
+    // each row is known.
+    let mut calling_bubbles = Vec::new();
+    let mut summarized = HashSet::new();
+
+    for &(org_name, calling) in &callings {
+        let repeats = callings
+            .iter()
+            .map(|&(_, c)| c)
+            .filter(|c| c.name == calling.name)
+            .collect::<Vec<_>>();
+
+        if let Some(collapse) = conf
+            .collapse_repeated_callings
+            .as_ref()
+            .filter(|collapse| repeats.len() >= collapse.min_count.max(2))
+        {
+            if summarized.insert(calling.name.as_str()) {
+                let summary_bubble_id = ids.summary_id(&calling.name, bubble_id);
+                calling_bubbles.push(summary_nodes(
+                    summary_bubble_id,
+                    bubble_id,
+                    &repeats,
+                    collapse,
+                    settings,
+                    conf,
+                ));
+            }
+            continue;
+        }
+
+        let calling_bubble_id = ids.calling_id(calling, bubble_id);
+        calling_bubbles.push(calling_nodes(
+            calling_bubble_id,
+            bubble_id,
+            calling,
+            org_name,
+            settings,
+            conf,
+        ));
+    }
+
+    for row in calling_bubbles.chunks_mut(calling_bubbles_per_row as usize) {
+        bubble_cursor_y += conf.calling_bubble_vertical_margins;
+
+        for (column, (node, _)) in row.iter_mut().enumerate() {
+            node.relative.x = calling_bubble_left_margin
+                + column as i32
+                    * (conf.calling_bubble_dimensions.width + calling_bubble_left_margin);
+            node.relative.y = bubble_cursor_y;
+        }
+
+        bubble_cursor_y += row
+            .iter()
+            .map(|(node, _)| node.relative.height)
+            .max()
+            .unwrap_or(0);
+    }
+
+    if !calling_bubbles.is_empty() {
+        bubble_cursor_y += conf.calling_bubble_vertical_margins;
+    }
+
+    for (node, badge) in calling_bubbles {
+        nodes.push(node);
+        nodes.extend(badge);
+    }
+
+    let sub_org_bubble_width = bubble_width - 2 * conf.sub_org_bubble_horzontal_margins;
+    let mut has_sub_org_bubbles = false;
+
+    let children: &[Organization] = if flatten { &[] } else { &org.children };
+
+    for child in children {
+        if !has_shown_callings(child, settings.show_vacancies, conf) {
+            continue;
+        }
+
+        let sub_org_bubble_id = ids.org_bubble_id(child, Some(bubble_id));
+
+        let (sub_org_bubble_contents, sub_org_bubble_height) = bubble_contents(
+            child,
+            &sub_org_bubble_id,
+            sub_org_bubble_width,
+            settings.grandchild_orgs == GrandchildOrgs::Flattened,
+            settings,
+            conf,
+            ids,
+        )?;
+
+        bubble_cursor_y += conf.sub_org_bubble_vertical_margins;
+
+        nodes.push(Node::new(
+            sub_org_bubble_id,
+            Some(bubble_id.to_string()),
+            Rect {
+                x: conf.sub_org_bubble_horzontal_margins,
+                y: bubble_cursor_y,
+                width: sub_org_bubble_width,
+                height: sub_org_bubble_height,
+            },
+            NodeKind::SubOrg {
+                name: child.name.clone(),
+            },
+            settings.sub_org_style.clone(),
+        ));
+        nodes.extend(sub_org_bubble_contents);
+
+        bubble_cursor_y += sub_org_bubble_height;
+        has_sub_org_bubbles = true;
+    }
+
+    if has_sub_org_bubbles {
+        bubble_cursor_y += conf.sub_org_bubble_vertical_margins;
+    }
+
+    Ok((nodes, bubble_cursor_y.max(min_bubble_height)))
+}
+
+// A calling bubble, and its not-set-apart badge if it has one. The bubble is positioned by the
+// caller.
+fn calling_nodes(
+    calling_bubble_id: String,
+    bubble_id: &str,
+    calling: &Calling,
+    org_name: &str,
+    settings: &OrgSettings,
+    conf: &Config,
+) -> (Node, Option<Node>) {
+    let mut style = match &calling.member {
+        Some(member) => {
+            let mut style = settings.calling_style.clone();
+
+            let tenure = member.held_calling_since.tenure_until(settings.as_of);
+            if let Some(bucket) = conf
+                .tenure_colors
+                .as_ref()
+                .and_then(|t| t.bucket(tenure.total_months()))
+            {
+                style.fill_color = bucket.color.clone();
+            }
+
+            style
+        }
+        None => settings.vacant_calling_style.clone(),
+    };
+    let badge = not_set_apart_overdue(calling, settings.as_of, conf)
+        .and_then(|overdue| mark_not_set_apart(&calling_bubble_id, overdue, &mut style, conf));
+
+    let node = Node::new(
+        calling_bubble_id,
+        Some(bubble_id.to_string()),
+        Rect {
+            x: 0,
+            y: 0,
+            width: conf.calling_bubble_dimensions.width,
+            height: conf.calling_bubble_dimensions.height,
+        },
+        calling_node_kind(calling, org_name, settings, conf),
+        style,
+    );
+
+    (node, badge)
+}
+
+// One bubble standing in for several callings with the same name, and its not-set-apart badge if
+// it has one. It's made taller than a calling bubble if that's needed to fit the list of names.
+//
+// If any of the members haven't been set apart, the bubble is marked the way a calling bubble would
+// be, in the overdue color if any of them are overdue. Tenure colors aren't used, since the members
+// can each have held the calling for a different length of time.
+fn summary_nodes(
+    summary_bubble_id: String,
+    bubble_id: &str,
+    callings: &[&Calling],
+    collapse: &CollapseRepeatedCallings,
+    settings: &OrgSettings,
+    conf: &Config,
+) -> (Node, Option<Node>) {
+    let filled = callings.iter().filter(|c| c.member.is_some()).count();
+    let vacant = callings.len() - filled;
+
+    let mut lines = vec![
+        TextLine::new(
+            format!("{} (×{})", callings[0].name, callings.len()),
+            TextStyle::Title,
+        ),
+        TextLine::new("", TextStyle::Body),
+    ];
+
+    if collapse.list_names {
+        lines.extend(
+            callings
+                .iter()
+                .filter_map(|c| c.member.as_ref())
+                .map(|m| TextLine::new(m.name.last_name_first(), TextStyle::Body)),
+        );
+    } else if filled > 0 {
+        lines.push(TextLine::new(format!("{} filled", filled), TextStyle::Body));
+    }
+
+    if vacant > 0 {
+        lines.push(TextLine::new(
+            format!("{} vacant", vacant),
+            TextStyle::Emphasis,
+        ));
+    }
+
+    let mut style = if filled == 0 {
+        settings.vacant_calling_style.clone()
+    } else {
+        settings.calling_style.clone()
+    };
+
+    let badge = callings
+        .iter()
+        .filter_map(|c| not_set_apart_overdue(c, settings.as_of, conf))
+        .max()
+        .and_then(|overdue| mark_not_set_apart(&summary_bubble_id, overdue, &mut style, conf));
+
+    let text_height: f64 = lines
+        .iter()
+        .map(|l| style.line_font_size(l.style) as f64 * LINE_HEIGHT)
+        .sum();
+    let height = conf
+        .calling_bubble_dimensions
+        .height
+        .max(text_height.ceil() as i32 + 2 * SUMMARY_BUBBLE_PADDING);
+
+    let node = Node::new(
+        summary_bubble_id,
+        Some(bubble_id.to_string()),
+        Rect {
+            x: 0,
+            y: 0,
+            width: conf.calling_bubble_dimensions.width,
+            height,
+        },
+        NodeKind::Calling { lines },
+        style,
+    );
+
+    (node, badge)
+}
+
+fn calling_node_kind(
+    calling: &Calling,
+    org_name: &str,
+    settings: &OrgSettings,
+    conf: &Config,
+) -> NodeKind {
+    let lines =
+        match settings
+            .calling_template
+            .render(calling, org_name, &conf.date_format, settings.as_of)
+        {
+            // The first line of the template is the bubble's title
+            Some(contents) => contents
+                .split('\n')
+                .enumerate()
+                .map(|(i, line)| {
+                    let style = if i == 0 {
+                        TextStyle::Title
+                    } else {
+                        TextStyle::Body
+                    };
+                    TextLine::new(line, style)
+                })
+                .collect(),
+            None => vec![
+                TextLine::new(calling.name.clone(), TextStyle::Title),
+                TextLine::new("", TextStyle::Body),
+                TextLine::new("Vacant", TextStyle::Emphasis),
+            ],
+        };
+
+    NodeKind::Calling { lines }
+}
+
+// Whether the calling is overdue to be set apart, or `None` if it doesn't need to be (it's vacant,
+// or its member has been set apart)
+fn not_set_apart_overdue(calling: &Calling, as_of: Date, conf: &Config) -> Option<bool> {
+    let member = calling.member.as_ref().filter(|m| !m.set_apart)?;
+
+    Some(
+        conf.not_set_apart
+            .overdue_after_days
+            .is_some_and(|days| member.held_calling_since.days_until(as_of) > days as i64),
+    )
+}
+
+// Marks a calling bubble as not set apart using `conf.not_set_apart.indicator`, either by changing
+// its border or by returning a badge to go on it
+fn mark_not_set_apart(
+    calling_bubble_id: &str,
+    overdue: bool,
+    style: &mut Style,
+    conf: &Config,
+) -> Option<Node> {
+    let not_set_apart = &conf.not_set_apart;
+    let color = if overdue {
+        not_set_apart.overdue_color.clone()
+    } else {
+        not_set_apart.color.clone()
+    };
+
+    match not_set_apart.indicator {
+        SetApartIndicator::None => None,
+        SetApartIndicator::Border => {
+            style.stroke_color = color;
+            style.stroke_width = style.stroke_width.max(3);
+            None
+        }
+        indicator @ (SetApartIndicator::Badge | SetApartIndicator::Icon) => {
+            Some(badge_node(calling_bubble_id, indicator, color, style, conf))
+        }
+    }
+}
+
+// Sits in the top right corner of the calling bubble
+fn badge_node(
+    calling_bubble_id: &str,
+    indicator: SetApartIndicator,
+    color: String,
+    calling_style: &Style,
+    conf: &Config,
+) -> Node {
+    let (text, width) = match indicator {
+        SetApartIndicator::Icon => ("!", 22),
+        _ => ("Not set apart", 100),
+    };
+    let height = 22;
+    let margin = 8;
+
+    Node::new(
+        format!("{}-badge", calling_bubble_id),
+        Some(calling_bubble_id.to_string()),
+        Rect {
+            x: conf.calling_bubble_dimensions.width - width - margin,
+            y: margin,
+            width,
+            height,
+        },
+        NodeKind::Badge {
+            text: text.to_string(),
+        },
+        Style {
+            fill_color: color.clone(),
+            stroke_color: color,
+            stroke_width: 1,
+            dashed: false,
+            rounded: true,
+            font_family: calling_style.font_family.clone(),
+            font_color: conf.not_set_apart.text_color.clone(),
+            font_size: 12,
+            title_font_size: 12,
+        },
+    )
+}
+
+// Deeply nested bubbles get narrower, so fit as many calling bubbles per row as the width allows
+// (up to the configured amount) while keeping the minimum horizontal margin between them
+fn calling_bubbles_per_row(bubble_width: i32, conf: &Config) -> Result<i32, LayoutError> {
+    (1..=conf.calling_bubbles_per_row)
+        .rev()
+        .find(|&count| {
+            let margin =
+                (bubble_width - conf.calling_bubble_dimensions.width * count) / (count + 1);
+            margin >= conf.calling_bubble_min_horizontal_margin
+        })
+        .ok_or(LayoutError::BubbleTooNarrow {
+            width: bubble_width,
+        })
+}
+
+fn shown_callings<'a>(
+    org: &'a Organization,
+    show_vacancies: bool,
+    conf: &Config,
+) -> Vec<&'a Calling> {
+    org.callings
+        .iter()
+        .filter(|c| is_shown(c, show_vacancies, conf))
+        .collect()
+}
+
+fn is_shown(calling: &Calling, show_vacancies: bool, conf: &Config) -> bool {
+    calling.member.is_some()
+        || (show_vacancies && !conf.hidden_vacant_callings.contains(&calling.name))
+}
+
+fn has_shown_callings(org: &Organization, show_vacancies: bool, conf: &Config) -> bool {
+    !shown_callings(org, show_vacancies, conf).is_empty()
+        || org
+            .children
+            .iter()
+            .any(|c| has_shown_callings(c, show_vacancies, conf))
+}
+
+struct IdGenerator {
+    // Only used for `IdScheme::Seeded`
+    rng: Option<StdRng>,
+    calling_num: usize,
+    used: HashSet<String>,
+}
+
+impl IdGenerator {
+    fn new(id_scheme: IdScheme) -> IdGenerator {
+        IdGenerator {
+            rng: match id_scheme {
+                IdScheme::Stable => None,
+                IdScheme::Seeded(seed) => Some(StdRng::seed_from_u64(seed)),
+            },
+            calling_num: 0,
+            used: HashSet::new(),
+        }
+    }
+
+    // `org_name` is the org the page is for, if it isn't the overview
+    fn scene_id(&mut self, org_name: Option<&str>) -> String {
+        match (&mut self.rng, org_name) {
+            (Some(rng), _) => rng.gen_range(0..u128::MAX).to_string(),
+            (None, Some(org_name)) => format!("callings-{}", slug(org_name)),
+            (None, None) => "callings".to_string(),
+        }
+    }
+
+    // e.g. "Elders-Quorum-12345" using the org's LCR id, or "Primary--Sunbeams" (its path) if it
+    // doesn't have one
+    fn org_bubble_id(&mut self, org: &Organization, parent_id: Option<&str>) -> String {
+        let id = match (&mut self.rng, org.id, parent_id) {
+            (Some(rng), _, _) => format!("{}-{}", slug(&org.name), rng.gen_range(100000..1000000)),
+            (None, Some(lcr_id), _) => format!("{}-{}", slug(&org.name), lcr_id),
+            (None, None, Some(parent_id)) => format!("{}--{}", parent_id, slug(&org.name)),
+            (None, None, None) => slug(&org.name),
+        };
+
+        self.unique(id)
+    }
+
+    // e.g. "calling-67890" using the calling's LCR position id, or "Primary--Sunbeams--Teacher" if
+    // it doesn't have one
+    fn calling_id(&mut self, calling: &Calling, parent_id: &str) -> String {
+        let id = match (&self.rng, calling.position_id) {
+            (Some(_), _) => {
+                self.calling_num += 1;
+                format!("calling-{}", self.calling_num - 1)
+            }
+            (None, Some(position_id)) => format!("calling-{}", position_id),
+            (None, None) => format!("{}--{}", parent_id, slug(&calling.name)),
+        };
+
+        self.unique(id)
+    }
+
+    // e.g. "Primary--CTR-4--Primary-Teacher" for the bubble summarizing a class's teachers
+    fn summary_id(&mut self, calling_name: &str, parent_id: &str) -> String {
+        let id = match &self.rng {
+            Some(_) => {
+                self.calling_num += 1;
+                format!("calling-{}", self.calling_num - 1)
+            }
+            None => format!("{}--{}", parent_id, slug(calling_name)),
+        };
+
+        self.unique(id)
+    }
+
+    // Repeated names (e.g. several vacant "Primary Teacher" callings) get a number added on
+    fn unique(&mut self, id: String) -> String {
+        let mut unique_id = id.clone();
+        let mut n = 2;
+
+        while !self.used.insert(unique_id.clone()) {
+            unique_id = format!("{}-{}", id, n);
+            n += 1;
+        }
+
+        unique_id
+    }
+}
+
+// Keeps ids readable in the draw.io file, e.g. "Sunday School & Youth" -> "Sunday-School-Youth"
+fn slug(name: &str) -> String {
+    name.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IdScheme, LayoutError, NodeKind, Scene, balance_columns, layout};
+    use crate::config::Config;
+    use lcr_core::{Calling, Date, Organization, ParseMode, orgs_from_lcr_data};
+    use serde_json::{Value, json};
+    use std::collections::HashMap;
+
+    const LCR_DATA: &str = r#"[
+        {"subOrgId": 1, "name": "Bishopric", "children": [], "callings": [
+            {"positionId": 11, "position": "Bishop", "memberName": "Smith, John", "activeDate": "20230301", "setApart": true},
+            {"positionId": 12, "position": "Ward Clerk", "memberName": null}
+        ]},
+        {"name": "Primary", "callings": [
+            {"positionId": 21, "position": "Primary President", "memberName": "Jones, Mary", "activeDate": "20220101", "setApart": true}
+        ], "children": [
+            {"name": "CTR 4", "children": [], "callings": [
+                {"position": "Primary Teacher", "memberName": "Young, Brigham", "activeDate": "20220101", "setApart": true},
+                {"position": "Primary Teacher", "memberName": "Lee, Sam", "activeDate": "20230101", "setApart": false}
+            ]}
+        ]},
+        {"subOrgId": 3, "name": "Sunday School", "children": [], "callings": [
+            {"positionId": 31, "position": "Sunday School President", "memberName": null},
+            {"positionId": 32, "position": "Sunday School Teacher", "memberName": null}
+        ]}
+    ]"#;
+
+    fn orgs() -> HashMap<String, Organization> {
+        orgs_from_lcr_data(LCR_DATA, ParseMode::Strict)
+            .unwrap()
+            .orgs
+    }
+
+    // The settings from the example config, plus `options`
+    fn config(org_ordering: &[&str], options: Value) -> Config {
+        let mut conf = json!({
+            "diagram_start_x": 0,
+            "diagram_start_y": 0,
+            "calling_bubbles_per_row": 3,
+            "org_bubble_width": 1250,
+            "org_bubble_title_height": 38,
+            "org_bubble_margins": { "top": 0, "right": 80, "bottom": 80, "left": 0 },
+            "sub_org_bubble_horzontal_margins": 25,
+            "sub_org_bubble_vertical_margins": 40,
+            "calling_bubble_vertical_margins": 25,
+            "calling_bubble_dimensions": { "width": 340, "height": 110 },
+            "calling_bubble_min_horizontal_margin": 20,
+            "org_ordering": org_ordering
+                .iter()
+                .map(|name| json!({ "name": name }))
+                .collect::<Vec<_>>(),
+        });
+        conf.as_object_mut()
+            .unwrap()
+            .extend(options.as_object().unwrap().clone());
+
+        serde_json::from_value(conf).unwrap()
+    }
+
+    fn as_of() -> Date {
+        "2024-01-01".parse().unwrap()
+    }
+
+    fn org_names(scene: &Scene) -> Vec<&str> {
+        scene
+            .nodes
+            .iter()
+            .filter_map(|n| match &n.kind {
+                NodeKind::Org { name } => Some(name.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn node_ids(scene: &Scene) -> Vec<&str> {
+        scene.nodes.iter().map(|n| n.id.as_str()).collect()
+    }
+
+    // The ids of an org's bubble and everything inside it
+    fn org_node_ids<'a>(scene: &'a Scene, org_name: &str) -> Vec<&'a str> {
+        let mut ids = Vec::new();
+
+        for node in &scene.nodes {
+            let is_org = matches!(&node.kind, NodeKind::Org { name } if name == org_name);
+            let in_org = node
+                .parent_id
+                .as_deref()
+                .is_some_and(|parent_id| ids.contains(&parent_id));
+
+            if is_org || in_org {
+                ids.push(node.id.as_str());
+            }
+        }
+
+        ids
+    }
+
+    #[test]
+    fn lays_out_orgs_with_only_vacant_callings() {
+        let orgs = orgs();
+        let conf = config(
+            &["Sunday School"],
+            json!({ "show_vacancies": true, "empty_orgs": "error" }),
+        );
+
+        let scene = layout(&orgs, &conf, IdScheme::Stable, as_of()).unwrap();
+        assert_eq!(org_node_ids(&scene, "Sunday School").len(), 3);
+    }
+
+    #[test]
+    fn applies_empty_orgs_to_orgs_with_nothing_to_show() {
+        let orgs = orgs();
+        let ordering = ["Bishopric", "Sunday School"];
+
+        let conf = config(&ordering, json!({ "empty_orgs": "skip" }));
+        let scene = layout(&orgs, &conf, IdScheme::Stable, as_of()).unwrap();
+        assert_eq!(org_names(&scene), vec!["Bishopric"]);
+
+        let conf = config(&ordering, json!({ "empty_orgs": "placeholder" }));
+        let scene = layout(&orgs, &conf, IdScheme::Stable, as_of()).unwrap();
+        assert_eq!(org_names(&scene), vec!["Bishopric", "Sunday School"]);
+        assert_eq!(org_node_ids(&scene, "Sunday School").len(), 1);
+
+        let conf = config(&ordering, json!({ "empty_orgs": "error" }));
+        assert!(matches!(
+            layout(&orgs, &conf, IdScheme::Stable, as_of()),
+            Err(LayoutError::EmptyOrg(name)) if name == "Sunday School"
+        ));
+    }
+
+    #[test]
+    fn applies_missing_orgs_to_orgs_not_in_the_input() {
+        let orgs = orgs();
+        let ordering = ["Bishopric", "Young Women"];
+
+        let conf = config(&ordering, json!({ "missing_orgs": "skip" }));
+        let scene = layout(&orgs, &conf, IdScheme::Stable, as_of()).unwrap();
+        assert_eq!(org_names(&scene), vec!["Bishopric"]);
+
+        let conf = config(&ordering, json!({ "missing_orgs": "placeholder" }));
+        let scene = layout(&orgs, &conf, IdScheme::Stable, as_of()).unwrap();
+        assert_eq!(org_names(&scene), vec!["Bishopric", "Young Women"]);
+        assert_eq!(org_node_ids(&scene, "Young Women").len(), 1);
+
+        let conf = config(&ordering, json!({ "missing_orgs": "error" }));
+        assert!(matches!(
+            layout(&orgs, &conf, IdScheme::Stable, as_of()),
+            Err(LayoutError::UnrecognizedOrg(name)) if name == "Young Women"
+        ));
+    }
+
+    #[test]
+    fn ids_are_the_same_every_run() {
+        let orgs = orgs();
+        let conf = config(&["Bishopric", "Primary"], json!({ "show_vacancies": true }));
+
+        for id_scheme in [IdScheme::Stable, IdScheme::Seeded(42)] {
+            let first = layout(&orgs, &conf, id_scheme, as_of()).unwrap();
+            let second = layout(&orgs, &conf, id_scheme, as_of()).unwrap();
+
+            assert_eq!(first.id, second.id);
+            assert_eq!(node_ids(&first), node_ids(&second));
+        }
+
+        let scene = layout(&orgs, &conf, IdScheme::Stable, as_of()).unwrap();
+        assert_eq!(
+            node_ids(&scene),
+            vec![
+                "Bishopric-1",
+                "calling-11",
+                "calling-12",
+                "Primary",
+                "calling-21",
+                "Primary--CTR-4",
+                "Primary--CTR-4--Primary-Teacher",
+                "Primary--CTR-4--Primary-Teacher-2",
+                "Primary--CTR-4--Primary-Teacher-2-badge",
+            ]
+        );
+    }
+
+    #[test]
+    fn adding_a_calling_leaves_other_ids_unchanged() {
+        let conf = config(&["Bishopric", "Primary"], json!({ "show_vacancies": true }));
+        let before = layout(&orgs(), &conf, IdScheme::Stable, as_of()).unwrap();
+
+        let mut orgs = orgs();
+        orgs.get_mut("Primary").unwrap().callings.insert(
+            0,
+            Calling {
+                position_id: Some(22),
+                position_type_id: None,
+                name: "Primary Secretary".to_string(),
+                display_sequence: None,
+                member: None,
+            },
+        );
+        let after = layout(&orgs, &conf, IdScheme::Stable, as_of()).unwrap();
+
+        assert_eq!(
+            org_node_ids(&before, "Bishopric"),
+            org_node_ids(&after, "Bishopric")
+        );
+
+        let primary_ids = org_node_ids(&after, "Primary");
+        assert_eq!(
+            primary_ids.len(),
+            org_node_ids(&before, "Primary").len() + 1
+        );
+        assert!(
+            org_node_ids(&before, "Primary")
+                .iter()
+                .all(|id| primary_ids.contains(id))
+        );
+    }
+
+    #[test]
+    fn summary_bubbles_are_marked_if_a_member_is_not_set_apart() {
+        let orgs = orgs();
+        let options = |indicator: &str| {
+            json!({
+                "collapse_repeated_callings": { "min_count": 2 },
+                "not_set_apart": { "indicator": indicator, "overdue_after_days": 30 },
+            })
+        };
+        let summary_id = "Primary--CTR-4--Primary-Teacher";
+
+        let conf = config(&["Primary"], options("badge"));
+        let scene = layout(&orgs, &conf, IdScheme::Stable, as_of()).unwrap();
+        let badge = scene
+            .nodes
+            .iter()
+            .find(|n| n.parent_id.as_deref() == Some(summary_id))
+            .unwrap();
+        assert!(matches!(badge.kind, NodeKind::Badge { .. }));
+        assert_eq!(badge.style.fill_color, conf.not_set_apart.overdue_color);
+
+        let conf = config(&["Primary"], options("border"));
+        let scene = layout(&orgs, &conf, IdScheme::Stable, as_of()).unwrap();
+        let summary = scene.nodes.iter().find(|n| n.id == summary_id).unwrap();
+        assert_eq!(summary.style.stroke_color, conf.not_set_apart.overdue_color);
+    }
+
+    #[test]
+    fn balance_columns_splits_evenly() {
+        assert_eq!(
+            balance_columns(&[2, 2, 2, 2], &[false; 4], 2),
+            vec![0, 0, 1, 1]
+        );
+        assert_eq!(
+            balance_columns(&[6, 1, 2, 3], &[false; 4], 2),
+            vec![0, 1, 1, 1]
+        );
+    }
+
+    #[test]
+    fn balance_columns_prefers_the_later_break_on_a_tie() {
+        assert_eq!(balance_columns(&[1, 1, 1], &[false; 3], 2), vec![0, 0, 1]);
+    }
+
+    #[test]
+    fn balance_columns_honors_manual_breaks() {
+        assert_eq!(
+            balance_columns(&[5, 1, 1, 1], &[false, false, true, false], 2),
+            vec![0, 0, 1, 1]
+        );
+
+        // More breaks than `column_count` allows for add columns
+        assert_eq!(
+            balance_columns(&[1, 1, 1, 1], &[false, true, true, true], 2),
+            vec![0, 1, 2, 3]
+        );
+    }
+
+    #[test]
+    fn balance_columns_uses_at_most_one_column_per_org() {
+        assert_eq!(balance_columns(&[3, 2], &[false; 2], 5), vec![0, 1]);
+        assert_eq!(balance_columns(&[], &[], 3), Vec::<usize>::new());
+    }
+
+    #[test]
+    fn balance_columns_ignores_a_break_on_the_first_org() {
+        assert_eq!(
+            balance_columns(&[1, 1, 1, 1], &[true, false, false, false], 2),
+            vec![0, 0, 1, 1]
+        );
+        assert_eq!(balance_columns(&[1, 1], &[true, false], 1), vec![0, 0]);
+    }
+}
@@ -2,20 +2,33 @@ use std::env;
 use std::fs;
 use std::process;
 
+mod args;
 mod config;
-mod data;
-mod generate;
-mod parse;
+mod layout;
+mod render;
+mod template;
 
-use generate::diagram_file_contents;
-use parse::orgs_from_lcr_data;
+use args::{Args, OutputFormat};
+use config::Config;
+use layout::{IdScheme, layout, layout_pages, unlisted_orgs};
+use lcr_core::filter::{Filter, PRESETS_FILE};
+use lcr_core::{ParseMode, orgs_from_lcr_data};
+use render::{DrawioRenderer, HtmlRenderer, Renderer, SvgRenderer};
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 3 {
-        eprintln!("invalid args\nusage: callings-diagram <input file> <output file>");
-        process::exit(1);
-    }
+    let args = match Args::parse(env::args().skip(1)) {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("invalid args: {}\n{}", err, args::USAGE);
+            process::exit(1);
+        }
+    };
+
+    let parse_mode = if args.strict {
+        ParseMode::Strict
+    } else {
+        ParseMode::Lenient
+    };
 
     let config_file_contents = match fs::read_to_string("diagram-config.json") {
         Ok(contents) => contents,
@@ -28,7 +41,7 @@ fn main() {
         }
     };
 
-    let conf = match serde_json::from_str(&config_file_contents) {
+    let conf: Config = match serde_json::from_str(&config_file_contents) {
         Ok(config) => config,
         Err(err) => {
             eprintln!("Failed to parse config file 'diagram-config.json': {}", err);
@@ -36,34 +49,113 @@ fn main() {
         }
     };
 
-    let input_file_contents = match fs::read_to_string(&args[1]) {
+    let filter = match &args.filter {
+        Some(name) => {
+            let presets = match fs::read_to_string(PRESETS_FILE) {
+                Ok(contents) => contents,
+                Err(err) => {
+                    eprintln!(
+                        "Expected '{}' file in current directory for --filter: {}",
+                        PRESETS_FILE, err
+                    );
+                    process::exit(1);
+                }
+            };
+
+            match Filter::preset(&presets, name) {
+                Ok(filter) => Some(filter),
+                Err(err) => {
+                    eprintln!("Failed to load filter from '{}': {}", PRESETS_FILE, err);
+                    process::exit(1);
+                }
+            }
+        }
+        None => None,
+    };
+
+    let input_file_contents = match fs::read_to_string(&args.input_file) {
         Ok(contents) => contents,
         Err(err) => {
-            eprintln!("Failed to read input file '{}': {}", args[1], err);
+            eprintln!("Failed to read input file '{}': {}", args.input_file, err);
             process::exit(1);
         }
     };
 
-    let orgs = match orgs_from_lcr_data(&input_file_contents) {
-        Ok(orgs) => orgs,
+    let mut parsed = match orgs_from_lcr_data(&input_file_contents, parse_mode) {
+        Ok(parsed) => parsed,
         Err(err) => {
-            eprintln!("Failed to parse input file '{}': {}", args[1], err);
+            eprintln!("Failed to parse input file '{}': {}", args.input_file, err);
             process::exit(1);
         }
     };
 
-    let output_file_contents = match diagram_file_contents(&orgs, &conf) {
-        Ok(contents) => contents,
+    for org in parsed.orgs.values_mut() {
+        if let Some(filter) = &filter {
+            filter.apply(org, args.as_of);
+        }
+        conf.calling_order.sort(org);
+    }
+
+    let id_scheme = match args.seed {
+        Some(seed) => IdScheme::Seeded(seed),
+        None => IdScheme::Stable,
+    };
+
+    let pages = if args.page_per_org {
+        layout_pages(&parsed.orgs, &conf, id_scheme, args.as_of)
+    } else {
+        layout(&parsed.orgs, &conf, id_scheme, args.as_of).map(|scene| vec![scene])
+    };
+
+    let pages = match pages {
+        Ok(pages) => pages,
         Err(err) => {
-            eprintln!("Failed to generate diagram file contents: {}", err);
+            eprintln!("Failed to lay out diagram: {}", err);
             process::exit(1);
         }
     };
 
-    if let Err(err) = fs::write(&args[2], output_file_contents) {
-        eprintln!("Failed to write to output file '{}': {}", args[2], err);
+    let renderer: &dyn Renderer = match args.format {
+        OutputFormat::Drawio => &DrawioRenderer,
+        OutputFormat::Svg => &SvgRenderer,
+        OutputFormat::Html => &HtmlRenderer,
+    };
+
+    let output_file_contents = renderer.render(&pages);
+
+    if let Err(err) = fs::write(&args.output_file, output_file_contents) {
+        eprintln!(
+            "Failed to write to output file '{}': {}",
+            args.output_file, err
+        );
         process::exit(1);
     }
 
-    println!("Successfully wrote diagram to {}", args[2]);
+    println!("Successfully wrote diagram to {}", args.output_file);
+
+    let unlisted = unlisted_orgs(&parsed.orgs, &conf);
+
+    if !unlisted.is_empty() {
+        eprintln!(
+            "\n{} org(s) in '{}' are not in org_ordering and were left off the diagram:",
+            unlisted.len(),
+            args.input_file
+        );
+
+        for name in unlisted {
+            eprintln!("  {}", name);
+        }
+    }
+
+    if !parsed.warnings.is_empty() {
+        eprintln!(
+            "\n{} record(s) in '{}' had problems:",
+            parsed.warnings.len(),
+            args.input_file
+        );
+
+        for warning in &parsed.warnings {
+            eprintln!("  {}", warning);
+        }
+    }
 }
@@ -0,0 +1,205 @@
+use super::Renderer;
+use super::escape_xml;
+use super::mxgraph::{DEFAULT_PARENT_ID, MxCell, MxDiagram, MxFile, MxGeometry, MxStyle};
+use crate::layout::{NodeKind, Scene, Style, TextStyle};
+
+pub struct DrawioRenderer;
+
+impl Renderer for DrawioRenderer {
+    fn render(&self, pages: &[Scene]) -> String {
+        let mut file = MxFile::new();
+
+        for scene in pages {
+            file.add_diagram(diagram(scene));
+        }
+
+        file.to_xml()
+    }
+}
+
+fn diagram(scene: &Scene) -> MxDiagram {
+    let mut diagram = MxDiagram::new(scene.id.clone(), scene.name.clone());
+
+    for node in &scene.nodes {
+        // Labels are HTML (`html=1`), so text goes in escaped and the markup around it doesn't
+        let (value, style) = match &node.kind {
+            NodeKind::Org { name } | NodeKind::SubOrg { name } => (
+                escape_xml(name),
+                MxStyle::new()
+                    .shape("swimlane")
+                    .set("whiteSpace", "wrap")
+                    .set("html", 1)
+                    .set("startSize", 40)
+                    .set("fontStyle", 1),
+            ),
+            NodeKind::Calling { lines } => {
+                let value = lines
+                    .iter()
+                    .map(|line| {
+                        let text = escape_xml(&line.text);
+
+                        let text = match line.style {
+                            _ if text.is_empty() => "<br>".to_string(),
+                            TextStyle::Title => format!(
+                                r#"<b><font style="font-size: {}px;">{}</font></b>"#,
+                                node.style.title_font_size, text
+                            ),
+                            TextStyle::Body => text,
+                            TextStyle::Emphasis => format!("<i>{}</i>", text),
+                        };
+
+                        format!("<div>{}</div>", text)
+                    })
+                    .collect::<String>();
+
+                let style = MxStyle::new()
+                    .set("whiteSpace", "wrap")
+                    .set("html", 1)
+                    .set("align", "left")
+                    .set("spacingLeft", 0)
+                    .set("spacingTop", 0)
+                    .set("spacing", 10);
+
+                (value, style)
+            }
+            NodeKind::Badge { text } => (
+                format!("<b>{}</b>", escape_xml(text)),
+                MxStyle::new()
+                    .set("whiteSpace", "wrap")
+                    .set("html", 1)
+                    .set("arcSize", 50)
+                    .set("spacing", 0),
+            ),
+        };
+
+        let style = with_style(style, &node.kind, &node.style);
+
+        let geometry = node.relative;
+
+        diagram.add_cell(MxCell {
+            id: node.id.clone(),
+            value,
+            style,
+            parent: node
+                .parent_id
+                .clone()
+                .unwrap_or_else(|| DEFAULT_PARENT_ID.to_string()),
+            geometry: MxGeometry {
+                x: geometry.x,
+                y: geometry.y,
+                width: geometry.width,
+                height: geometry.height,
+            },
+        });
+    }
+
+    diagram
+}
+
+// Adds the themed parts of a bubble's style
+fn with_style(mx_style: MxStyle, kind: &NodeKind, style: &Style) -> MxStyle {
+    let font_size = match kind {
+        NodeKind::Org { .. } | NodeKind::SubOrg { .. } => style.title_font_size,
+        NodeKind::Calling { .. } | NodeKind::Badge { .. } => style.font_size,
+    };
+
+    let mx_style = mx_style
+        .set("rounded", style.rounded as i32)
+        .set("fillColor", &style.fill_color)
+        .set("strokeColor", &style.stroke_color)
+        .set("strokeWidth", style.stroke_width)
+        .set("fontColor", &style.font_color)
+        .set("fontFamily", &style.font_family)
+        .set("fontSize", font_size);
+
+    let mx_style = match kind {
+        // Swimlanes only use `fillColor` for their title bar
+        NodeKind::Org { .. } | NodeKind::SubOrg { .. } => {
+            mx_style.set("swimlaneFillColor", &style.fill_color)
+        }
+        NodeKind::Calling { .. } | NodeKind::Badge { .. } => mx_style,
+    };
+
+    if style.dashed {
+        mx_style.set("dashed", 1)
+    } else {
+        mx_style
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::layout::{Node, Rect, TextLine};
+
+    fn style() -> Style {
+        Style {
+            fill_color: "#FFFFFF".to_string(),
+            stroke_color: "#000000".to_string(),
+            stroke_width: 1,
+            dashed: false,
+            rounded: true,
+            font_family: "Helvetica".to_string(),
+            font_color: "#000000".to_string(),
+            font_size: 16,
+            title_font_size: 18,
+        }
+    }
+
+    #[test]
+    fn escapes_labels_exactly_once() {
+        let rect = Rect {
+            x: 0,
+            y: 0,
+            width: 340,
+            height: 110,
+        };
+        let scene = Scene {
+            id: "callings".to_string(),
+            name: "Bishopric & Clerks".to_string(),
+            nodes: vec![Node {
+                id: "calling-1".to_string(),
+                parent_id: None,
+                relative: rect,
+                absolute: rect,
+                kind: NodeKind::Calling {
+                    lines: vec![
+                        TextLine {
+                            text: r#"Ward "Exec" Secretary & Clerk <x>"#.to_string(),
+                            style: TextStyle::Title,
+                        },
+                        TextLine {
+                            text: "O'Neil, Pat".to_string(),
+                            style: TextStyle::Body,
+                        },
+                    ],
+                },
+                style: style(),
+            }],
+        };
+
+        let xml = DrawioRenderer.render(&[scene]);
+        let document = roxmltree::Document::parse(&xml).expect("output should be valid XML");
+
+        let diagram = document
+            .descendants()
+            .find(|n| n.has_tag_name("diagram"))
+            .unwrap();
+        assert_eq!(diagram.attribute("name"), Some("Bishopric & Clerks"));
+
+        // roxmltree undoes the attribute escaping, leaving the HTML label draw.io reads
+        let cell = document
+            .descendants()
+            .find(|n| n.attribute("id") == Some("calling-1"))
+            .unwrap();
+        assert_eq!(
+            cell.attribute("value"),
+            Some(concat!(
+                r#"<div><b><font style="font-size: 18px;">"#,
+                "Ward &quot;Exec&quot; Secretary &amp; Clerk &lt;x&gt;",
+                "</font></b></div>",
+                "<div>O&apos;Neil, Pat</div>",
+            ))
+        );
+    }
+}
@@ -0,0 +1,131 @@
+use super::{Renderer, escape_xml};
+use crate::layout::{NodeKind, Scene, Style, TextStyle};
+
+// Layout that matches the draw.io styles used by `DrawioRenderer`. Colors and fonts come from each
+// bubble's style.
+const STYLESHEET: &str = r#"
+    body { margin: 0; }
+    h1 { margin: 16px 4px; font-family: Helvetica, Arial, sans-serif; }
+    .diagram { position: relative; margin: 4px; break-after: page; }
+    .bubble { position: absolute; box-sizing: border-box; }
+    .org-title { height: 40px; line-height: 40px; box-sizing: border-box; border-bottom-style: inherit; border-bottom-color: inherit; text-align: center; font-weight: bold; }
+    .calling { display: flex; flex-direction: column; justify-content: center; padding: 10px; }
+    .calling .title { font-weight: bold; }
+    .calling .emphasis { font-style: italic; }
+    .badge { display: flex; align-items: center; justify-content: center; font-weight: bold; }
+"#;
+
+pub struct HtmlRenderer;
+
+impl Renderer for HtmlRenderer {
+    fn render(&self, pages: &[Scene]) -> String {
+        let mut file_contents = format!(
+            r#"<!DOCTYPE html>
+<html>
+<head>
+  <meta charset="utf-8">
+  <title>Callings</title>
+  <style>{}  </style>
+</head>
+<body>"#,
+            STYLESHEET
+        );
+
+        for scene in pages {
+            if pages.len() > 1 {
+                file_contents.push_str(&format!("\n  <h1>{}</h1>", escape_xml(&scene.name)));
+            }
+
+            push_page(&mut file_contents, scene);
+        }
+
+        file_contents.push_str("\n</body>\n</html>\n");
+
+        file_contents
+    }
+}
+
+fn push_page(file_contents: &mut String, scene: &Scene) {
+    let bounds = scene.bounds();
+
+    file_contents.push_str(&format!(
+        r#"
+  <div class="diagram" style="width: {}px; height: {}px;">"#,
+        bounds.right(),
+        bounds.bottom()
+    ));
+
+    // Bubbles are positioned on the page rather than nested inside each other, which keeps
+    // the markup the same shape as the scene
+    for node in &scene.nodes {
+        let style = &node.style;
+
+        let (class, corner_radius, contents) = match &node.kind {
+            NodeKind::Org { name } | NodeKind::SubOrg { name } => (
+                "org",
+                10,
+                format!(
+                    r#"<div class="org-title" style="font-size: {}px; border-bottom-width: {}px;">{}</div>"#,
+                    style.title_font_size,
+                    style.stroke_width,
+                    escape_xml(name)
+                ),
+            ),
+            NodeKind::Calling { lines } => {
+                let contents = lines
+                    .iter()
+                    .map(|line| {
+                        if line.text.is_empty() {
+                            return "<div><br></div>".to_string();
+                        }
+
+                        match line.style {
+                            TextStyle::Title => format!(
+                                r#"<div class="title" style="font-size: {}px;">{}</div>"#,
+                                style.title_font_size,
+                                escape_xml(&line.text)
+                            ),
+                            TextStyle::Body => format!("<div>{}</div>", escape_xml(&line.text)),
+                            TextStyle::Emphasis => {
+                                format!(r#"<div class="emphasis">{}</div>"#, escape_xml(&line.text))
+                            }
+                        }
+                    })
+                    .collect::<String>();
+
+                ("calling", 16, contents)
+            }
+            NodeKind::Badge { text } => ("badge", node.absolute.height / 2, escape_xml(text)),
+        };
+
+        let rect = node.absolute;
+
+        file_contents.push_str(&format!(
+            r#"
+    <div class="bubble {}" style="left: {}px; top: {}px; width: {}px; height: {}px; {}">{}</div>"#,
+            class,
+            rect.x,
+            rect.y,
+            rect.width,
+            rect.height,
+            escape_xml(&css(style, corner_radius)),
+            contents
+        ));
+    }
+
+    file_contents.push_str("\n  </div>");
+}
+
+fn css(style: &Style, corner_radius: i32) -> String {
+    format!(
+        "background: {}; border: {}px {} {}; border-radius: {}px; color: {}; font-family: {}; font-size: {}px;",
+        style.fill_color,
+        style.stroke_width,
+        if style.dashed { "dashed" } else { "solid" },
+        style.stroke_color,
+        if style.rounded { corner_radius } else { 0 },
+        style.font_color,
+        style.font_family,
+        style.font_size,
+    )
+}
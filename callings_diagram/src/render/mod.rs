@@ -0,0 +1,24 @@
+use crate::layout::Scene;
+
+pub mod drawio;
+pub mod html;
+pub mod mxgraph;
+pub mod svg;
+
+pub use drawio::DrawioRenderer;
+pub use html::HtmlRenderer;
+pub use svg::SvgRenderer;
+
+// An output format for a laid out diagram. Renderers only decide how things look; where they go is
+// decided by `layout::layout`. A diagram has one or more pages.
+pub trait Renderer {
+    fn render(&self, pages: &[Scene]) -> String;
+}
+
+pub(crate) fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
@@ -0,0 +1,164 @@
+use super::escape_xml;
+use std::collections::HashMap;
+
+// Id of the cell every top-level cell is placed in. draw.io expects cell "0" to be the root and
+// cell "1" to be the default layer inside it.
+pub const DEFAULT_PARENT_ID: &str = "1";
+
+// A draw.io file, which holds one or more pages ("diagrams")
+#[derive(Default)]
+pub struct MxFile {
+    diagrams: Vec<MxDiagram>,
+}
+
+impl MxFile {
+    pub fn new() -> MxFile {
+        MxFile::default()
+    }
+
+    pub fn add_diagram(&mut self, diagram: MxDiagram) {
+        self.diagrams.push(diagram);
+    }
+
+    pub fn to_xml(&self) -> String {
+        let mut xml = String::from(
+            r#"<mxfile host="app.diagrams.net" agent="Mozilla/5.0 (Macintosh; Intel Mac OS X 10_15_7) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/18.2 Safari/605.1.15" version="26.0.5">"#,
+        );
+
+        for diagram in &self.diagrams {
+            diagram.write_xml(&mut xml);
+        }
+
+        xml.push_str("\n</mxfile>");
+        xml
+    }
+}
+
+pub struct MxDiagram {
+    id: String,
+    name: String,
+    cells: Vec<MxCell>,
+}
+
+impl MxDiagram {
+    pub fn new(id: impl Into<String>, name: impl Into<String>) -> MxDiagram {
+        MxDiagram {
+            id: id.into(),
+            name: name.into(),
+            cells: Vec::new(),
+        }
+    }
+
+    // Cells must be added after the cell they're placed in
+    pub fn add_cell(&mut self, cell: MxCell) {
+        self.cells.push(cell);
+    }
+
+    fn write_xml(&self, xml: &mut String) {
+        xml.push_str(&format!(
+            r#"
+  <diagram id="{}" name="{}">
+    <mxGraphModel grid="1" page="0" gridSize="10" guides="1" tooltips="1" connect="1" arrows="1" fold="1" pageScale="1" pageWidth="827" pageHeight="1169" math="0" shadow="0">
+      <root>
+        <mxCell id="0" />
+        <mxCell id="{}" parent="0" />"#,
+            escape_xml(&self.id),
+            escape_xml(&self.name),
+            DEFAULT_PARENT_ID,
+        ));
+
+        // Indent cells according to how deeply they are nested so the file is easier to read
+        let mut depths: HashMap<&str, usize> = HashMap::new();
+
+        for cell in &self.cells {
+            let depth = depths.get(cell.parent.as_str()).map_or(0, |d| d + 1);
+            depths.insert(&cell.id, depth);
+
+            let indent = " ".repeat(8 + 2 * depth);
+            let geometry = &cell.geometry;
+
+            xml.push_str(&format!(
+                r#"
+{indent}<mxCell id="{}" value="{}" style="{}" vertex="1" parent="{}">
+{indent}  <mxGeometry x="{}" y="{}" width="{}" height="{}" as="geometry" />
+{indent}</mxCell>"#,
+                escape_xml(&cell.id),
+                escape_xml(&cell.value),
+                escape_xml(&cell.style.to_string()),
+                escape_xml(&cell.parent),
+                geometry.x,
+                geometry.y,
+                geometry.width,
+                geometry.height,
+            ));
+        }
+
+        xml.push_str(
+            r#"
+      </root>
+    </mxGraphModel>
+  </diagram>"#,
+        );
+    }
+}
+
+// A vertex. `value` is the cell's label exactly as draw.io should see it (HTML markup if the style
+// has `html=1`); it is escaped when the file is written.
+pub struct MxCell {
+    pub id: String,
+    pub value: String,
+    pub style: MxStyle,
+    pub parent: String,
+    pub geometry: MxGeometry,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MxGeometry {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+// A draw.io style string, e.g. "swimlane;rounded=1;strokeWidth=4;"
+#[derive(Debug, Clone, Default)]
+pub struct MxStyle {
+    entries: Vec<(String, Option<String>)>,
+}
+
+impl MxStyle {
+    pub fn new() -> MxStyle {
+        MxStyle::default()
+    }
+
+    // A shape name like "swimlane", which has no value
+    pub fn shape(mut self, name: &str) -> MxStyle {
+        self.entries.push((name.to_string(), None));
+        self
+    }
+
+    // Replaces the value if `key` is already set
+    pub fn set(mut self, key: &str, value: impl ToString) -> MxStyle {
+        let value = Some(value.to_string());
+
+        match self.entries.iter_mut().find(|(k, _)| k == key) {
+            Some(entry) => entry.1 = value,
+            None => self.entries.push((key.to_string(), value)),
+        }
+
+        self
+    }
+}
+
+impl std::fmt::Display for MxStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (key, value) in &self.entries {
+            match value {
+                Some(value) => write!(f, "{}={};", key, value)?,
+                None => write!(f, "{};", key)?,
+            }
+        }
+
+        Ok(())
+    }
+}
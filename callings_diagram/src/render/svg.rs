@@ -0,0 +1,175 @@
+use super::{Renderer, escape_xml};
+use crate::layout::{LINE_HEIGHT, Node, NodeKind, Rect, Scene, TextStyle};
+
+// Matches the look of the draw.io styles used by `DrawioRenderer`
+const ORG_BUBBLE_HEADER_HEIGHT: i32 = 40;
+const CALLING_BUBBLE_PADDING: i32 = 10;
+const PAGE_GAP: i32 = 200;
+
+pub struct SvgRenderer;
+
+impl Renderer for SvgRenderer {
+    // SVG has no pages, so they are stacked one above the other
+    fn render(&self, pages: &[Scene]) -> String {
+        // Leave room for the stroke on the outermost bubbles
+        let padding = pages
+            .iter()
+            .flat_map(|scene| &scene.nodes)
+            .map(|n| n.style.stroke_width)
+            .max()
+            .unwrap_or(0);
+
+        let mut page_elems = String::new();
+        let mut width = 0;
+        let mut height = 0;
+
+        for (i, scene) in pages.iter().enumerate() {
+            let bounds = scene.bounds();
+
+            if i > 0 {
+                height += PAGE_GAP;
+            }
+
+            // Moves the page's top left corner to the left edge of the image, below the previous
+            // page
+            page_elems.push_str(&format!(
+                "\n<g transform=\"translate({} {})\">{}\n</g>",
+                padding - bounds.x,
+                height + padding - bounds.y,
+                page_elems_of(scene)
+            ));
+
+            width = width.max(bounds.width + 2 * padding);
+            height += bounds.height + 2 * padding;
+        }
+
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}">{}
+</svg>
+"#,
+            width, height, width, height, page_elems
+        )
+    }
+}
+
+fn page_elems_of(scene: &Scene) -> String {
+    let mut file_contents = String::new();
+
+    for node in &scene.nodes {
+        let elem = match &node.kind {
+            NodeKind::Org { name } | NodeKind::SubOrg { name } => org_bubble_elem(node, name),
+            NodeKind::Calling { lines } => {
+                let style = &node.style;
+                let Rect { x, y, height, .. } = node.absolute;
+
+                let mut elem = rect_elem(node, if style.rounded { 16 } else { 0 });
+
+                // draw.io centers the text block vertically within the bubble
+                let text_height: f64 = lines
+                    .iter()
+                    .map(|l| style.line_font_size(l.style) as f64 * LINE_HEIGHT)
+                    .sum();
+                let mut line_top = y as f64 + (height as f64 - text_height) / 2.0;
+
+                for line in lines {
+                    let font_size = style.line_font_size(line.style);
+                    let line_height = font_size as f64 * LINE_HEIGHT;
+                    // Roughly where the baseline of the font sits within the line box
+                    let baseline_y = line_top + line_height / 2.0 + font_size as f64 * 0.35;
+                    line_top += line_height;
+
+                    if line.text.is_empty() {
+                        continue;
+                    }
+
+                    let font_attrs = match line.style {
+                        TextStyle::Title => r#" font-weight="bold""#,
+                        TextStyle::Body => "",
+                        TextStyle::Emphasis => r#" font-style="italic""#,
+                    };
+
+                    elem.push_str(&format!(
+                            "\n  <text x=\"{}\" y=\"{:.1}\" font-family=\"{}\" font-size=\"{}\"{} fill=\"{}\">{}</text>",
+                            x + CALLING_BUBBLE_PADDING,
+                            baseline_y,
+                            escape_xml(&style.font_family),
+                            font_size,
+                            font_attrs,
+                            escape_xml(&style.font_color),
+                            escape_xml(&line.text),
+                        ));
+                }
+
+                elem
+            }
+            NodeKind::Badge { text } => {
+                let style = &node.style;
+                let rect = node.absolute;
+
+                format!(
+                    r#"{}
+  <text x="{}" y="{}" font-family="{}" font-size="{}" font-weight="bold" text-anchor="middle" dominant-baseline="central" fill="{}">{}</text>"#,
+                    rect_elem(node, if style.rounded { rect.height / 2 } else { 0 }),
+                    rect.x + rect.width / 2,
+                    rect.y + rect.height / 2,
+                    escape_xml(&style.font_family),
+                    style.font_size,
+                    escape_xml(&style.font_color),
+                    escape_xml(text),
+                )
+            }
+        };
+
+        file_contents.push('\n');
+        file_contents.push_str(&elem);
+    }
+
+    file_contents
+}
+
+fn rect_elem(node: &Node, corner_radius: i32) -> String {
+    let style = &node.style;
+    let rect = node.absolute;
+
+    format!(
+        r#"  <rect x="{}" y="{}" width="{}" height="{}" rx="{}" fill="{}" stroke="{}" stroke-width="{}"{} />"#,
+        rect.x,
+        rect.y,
+        rect.width,
+        rect.height,
+        corner_radius,
+        escape_xml(&style.fill_color),
+        escape_xml(&style.stroke_color),
+        style.stroke_width,
+        if style.dashed {
+            r#" stroke-dasharray="6 4""#
+        } else {
+            ""
+        },
+    )
+}
+
+fn org_bubble_elem(node: &Node, name: &str) -> String {
+    let style = &node.style;
+    let rect = node.absolute;
+
+    format!(
+        r#"{}
+  <line x1="{}" y1="{}" x2="{}" y2="{}" stroke="{}" stroke-width="{}" />
+  <text x="{}" y="{}" font-family="{}" font-size="{}" font-weight="bold" text-anchor="middle" dominant-baseline="central" fill="{}">{}</text>"#,
+        rect_elem(node, if style.rounded { 10 } else { 0 }),
+        rect.x,
+        rect.y + ORG_BUBBLE_HEADER_HEIGHT,
+        rect.right(),
+        rect.y + ORG_BUBBLE_HEADER_HEIGHT,
+        escape_xml(&style.stroke_color),
+        style.stroke_width,
+        rect.x + rect.width / 2,
+        rect.y + ORG_BUBBLE_HEADER_HEIGHT / 2,
+        escape_xml(&style.font_family),
+        style.title_font_size,
+        escape_xml(&style.font_color),
+        escape_xml(name),
+    )
+}
@@ -0,0 +1,344 @@
+use lcr_core::Date;
+use lcr_core::data::Calling;
+use std::fmt;
+
+// What goes on a calling bubble that has someone in it, e.g.
+//
+//   {calling}\n{given} {last}[\n{phone}]\nSince {since:%b %Y}
+//
+// `{field}` is replaced with one of the member's or calling's details, and `{since:...}` formats
+// the date with a custom pattern. Anything inside `[...]` is left out if any field inside it has
+// no value, e.g. `[Phone: {phone}]` for a member without a phone number. `\` escapes the next
+// character. The first line is drawn as the bubble's title.
+pub const DEFAULT_CALLING_TEMPLATE: &str = "{calling}\n\n{last_name_first}\nSince: {since}";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TemplateError {
+    UnknownField(String),
+    UnexpectedFormat(String),
+    Unclosed(char),
+    Unopened(char),
+}
+
+impl std::error::Error for TemplateError {}
+
+impl fmt::Display for TemplateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TemplateError::UnknownField(name) => write!(f, "Unknown field: {{{}}}", name),
+            TemplateError::UnexpectedFormat(name) => {
+                write!(f, "Field {{{}}} doesn't take a format", name)
+            }
+            TemplateError::Unclosed(c) => write!(f, "Unclosed '{}'", c),
+            TemplateError::Unopened(c) => write!(f, "Unexpected '{}'", c),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Calling,
+    Org,
+    Name,
+    LastNameFirst,
+    Given,
+    Last,
+    Suffix,
+    Email,
+    Phone,
+    Since,
+    Tenure,
+    SetApart,
+}
+
+impl Field {
+    fn from_name(name: &str) -> Option<Field> {
+        let field = match name {
+            "calling" => Field::Calling,
+            "org" => Field::Org,
+            "name" => Field::Name,
+            "last_name_first" => Field::LastNameFirst,
+            "given" => Field::Given,
+            "last" => Field::Last,
+            "suffix" => Field::Suffix,
+            "email" => Field::Email,
+            "phone" => Field::Phone,
+            "since" => Field::Since,
+            "tenure" => Field::Tenure,
+            "set_apart" => Field::SetApart,
+            _ => return None,
+        };
+
+        Some(field)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Text(String),
+    Field(Field, Option<String>),
+    Section(Vec<Part>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    parts: Vec<Part>,
+}
+
+impl Template {
+    pub fn parse(template: &str) -> Result<Template, TemplateError> {
+        let mut chars = template.chars();
+        let parts = parse_parts(&mut chars, false)?;
+
+        Ok(Template { parts })
+    }
+
+    // `org` is the name of the org or sub-org the calling is in, and `{tenure}` is measured up to
+    // `as_of`. Returns `None` for a vacant calling.
+    pub fn render(
+        &self,
+        calling: &Calling,
+        org: &str,
+        date_format: &str,
+        as_of: Date,
+    ) -> Option<String> {
+        let member = calling.member.as_ref()?;
+
+        let value = |field: Field, format: Option<&str>| -> Option<String> {
+            let value = match field {
+                Field::Calling => calling.name.clone(),
+                Field::Org => org.to_string(),
+                Field::Name => member.name.display_name(),
+                Field::LastNameFirst => member.name.last_name_first(),
+                Field::Given => member.name.given_names.clone(),
+                Field::Last => member.name.last_name.clone(),
+                Field::Suffix => member.name.suffix.clone()?,
+                Field::Email => member.email.clone()?,
+                Field::Phone => member.phone.clone()?,
+                Field::Since => member
+                    .held_calling_since
+                    .format(format.unwrap_or(date_format)),
+                Field::Tenure => member.held_calling_since.tenure_until(as_of).to_string(),
+                Field::SetApart if member.set_apart => "Set apart".to_string(),
+                Field::SetApart => return None,
+            };
+
+            Some(value).filter(|v| !v.trim().is_empty())
+        };
+
+        let mut rendered = String::new();
+        render_parts(&self.parts, &value, &mut rendered);
+
+        Some(rendered)
+    }
+}
+
+// Stops at the end of the template, or at the `]` closing the section being parsed
+fn parse_parts(
+    chars: &mut std::str::Chars<'_>,
+    in_section: bool,
+) -> Result<Vec<Part>, TemplateError> {
+    let mut parts = Vec::new();
+    let mut text = String::new();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' => text.push(chars.next().unwrap_or('\\')),
+            '{' => {
+                let mut field = String::new();
+
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => field.push(c),
+                        None => return Err(TemplateError::Unclosed('{')),
+                    }
+                }
+
+                let (name, format) = match field.split_once(':') {
+                    Some((name, format)) => (name.trim(), Some(format.to_string())),
+                    None => (field.trim(), None),
+                };
+
+                let field = Field::from_name(name)
+                    .ok_or_else(|| TemplateError::UnknownField(name.to_string()))?;
+
+                if format.is_some() && field != Field::Since {
+                    return Err(TemplateError::UnexpectedFormat(name.to_string()));
+                }
+
+                if !text.is_empty() {
+                    parts.push(Part::Text(std::mem::take(&mut text)));
+                }
+                parts.push(Part::Field(field, format));
+            }
+            '}' => return Err(TemplateError::Unopened('}')),
+            '[' => {
+                if !text.is_empty() {
+                    parts.push(Part::Text(std::mem::take(&mut text)));
+                }
+                parts.push(Part::Section(parse_parts(chars, true)?));
+            }
+            ']' if in_section => {
+                if !text.is_empty() {
+                    parts.push(Part::Text(text));
+                }
+                return Ok(parts);
+            }
+            ']' => return Err(TemplateError::Unopened(']')),
+            c => text.push(c),
+        }
+    }
+
+    if in_section {
+        return Err(TemplateError::Unclosed('['));
+    }
+
+    if !text.is_empty() {
+        parts.push(Part::Text(text));
+    }
+
+    Ok(parts)
+}
+
+// Returns whether every field had a value
+fn render_parts(
+    parts: &[Part],
+    value: &dyn Fn(Field, Option<&str>) -> Option<String>,
+    rendered: &mut String,
+) -> bool {
+    let mut complete = true;
+
+    for part in parts {
+        match part {
+            Part::Text(text) => rendered.push_str(text),
+            Part::Field(field, format) => match value(*field, format.as_deref()) {
+                Some(value) => rendered.push_str(&value),
+                None => complete = false,
+            },
+            Part::Section(parts) => {
+                let mut section = String::new();
+
+                if render_parts(parts, value, &mut section) {
+                    rendered.push_str(&section);
+                }
+            }
+        }
+    }
+
+    complete
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lcr_core::data::MemberWithCalling;
+    use lcr_core::{MemberName, date::LONG_FORMAT};
+
+    fn calling(phone: Option<&str>) -> Calling {
+        Calling {
+            position_id: Some(1),
+            position_type_id: None,
+            name: "Bishop".to_string(),
+            display_sequence: None,
+            member: Some(MemberWithCalling {
+                id: None,
+                legacy_cmis_id: None,
+                name: MemberName {
+                    last_name: "Smith".to_string(),
+                    given_names: "John".to_string(),
+                    suffix: None,
+                },
+                email: None,
+                phone: phone.map(str::to_string),
+                held_calling_since: Date::new(2023, 3, 1).unwrap(),
+                set_apart: true,
+            }),
+        }
+    }
+
+    fn render(template: &str, calling: &Calling) -> Option<String> {
+        Template::parse(template).unwrap().render(
+            calling,
+            "Bishopric",
+            LONG_FORMAT,
+            Date::new(2024, 7, 1).unwrap(),
+        )
+    }
+
+    #[test]
+    fn default_template_matches_the_original_bubble_text() {
+        assert_eq!(
+            render(DEFAULT_CALLING_TEMPLATE, &calling(None)).unwrap(),
+            "Bishop\n\nSmith, John\nSince: 2023, March 1st"
+        );
+    }
+
+    #[test]
+    fn sections_are_left_out_when_a_field_has_no_value() {
+        let template = "{given} {last}[\nPhone: {phone}]";
+
+        assert_eq!(render(template, &calling(None)).unwrap(), "John Smith");
+        assert_eq!(
+            render(template, &calling(Some("555-1234"))).unwrap(),
+            "John Smith\nPhone: 555-1234"
+        );
+        assert_eq!(
+            render("[{org}[ ({phone})]]", &calling(None)).unwrap(),
+            "Bishopric"
+        );
+    }
+
+    #[test]
+    fn escapes_are_written_as_is() {
+        assert_eq!(
+            render(r"\{calling\} \[{calling}\] \\", &calling(None)).unwrap(),
+            r"{calling} [Bishop] \"
+        );
+    }
+
+    #[test]
+    fn since_takes_a_date_format() {
+        assert_eq!(
+            render("{since:%b %Y} / {since} / {tenure}", &calling(None)).unwrap(),
+            "Mar 2023 / 2023, March 1st / 1y 4m"
+        );
+    }
+
+    #[test]
+    fn vacant_callings_render_nothing() {
+        let vacant = Calling {
+            member: None,
+            ..calling(None)
+        };
+        assert_eq!(render(DEFAULT_CALLING_TEMPLATE, &vacant), None);
+    }
+
+    #[test]
+    fn reports_template_errors() {
+        assert_eq!(
+            Template::parse("{nickname}"),
+            Err(TemplateError::UnknownField("nickname".to_string()))
+        );
+        assert_eq!(
+            Template::parse("{calling:%Y}"),
+            Err(TemplateError::UnexpectedFormat("calling".to_string()))
+        );
+        assert_eq!(
+            Template::parse("{calling"),
+            Err(TemplateError::Unclosed('{'))
+        );
+        assert_eq!(
+            Template::parse("[{phone}"),
+            Err(TemplateError::Unclosed('['))
+        );
+        assert_eq!(
+            Template::parse("{phone}]"),
+            Err(TemplateError::Unopened(']'))
+        );
+        assert_eq!(
+            Template::parse("calling}"),
+            Err(TemplateError::Unopened('}'))
+        );
+    }
+}
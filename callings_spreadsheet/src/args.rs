@@ -0,0 +1,71 @@
+use lcr_core::{Date, ParseMode};
+
+pub const USAGE: &str = "usage: callings-spreadsheet [--include-vacant] [--strict] [--as-of <YYYY-MM-DD>] [--tenure-buckets] [--calling-order <rules file>] [--filter <preset>] <input_file> <output_file>";
+
+#[derive(Debug)]
+pub struct Args {
+    pub input_file_path: String,
+    pub output_file_path: String,
+    pub include_vacant: bool,
+    pub parse_mode: ParseMode,
+    pub as_of: Date,
+    pub tenure_buckets: bool,
+    pub calling_order_file_path: Option<String>,
+    pub filter: Option<String>,
+}
+
+impl Args {
+    pub fn parse(mut args: impl Iterator<Item = String>) -> Result<Args, String> {
+        let mut include_vacant = false;
+        let mut parse_mode = ParseMode::Lenient;
+        let mut as_of = None;
+        let mut tenure_buckets = false;
+        let mut calling_order_file_path = None;
+        let mut filter = None;
+        let mut paths = Vec::new();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--include-vacant" => include_vacant = true,
+                "--strict" => parse_mode = ParseMode::Strict,
+                "--tenure-buckets" => tenure_buckets = true,
+                "--as-of" => {
+                    let date = args.next().ok_or("--as-of requires a date")?;
+                    as_of = Some(
+                        date.parse::<Date>()
+                            .map_err(|err| format!("invalid --as-of date: {}", err))?,
+                    );
+                }
+                "--calling-order" => {
+                    calling_order_file_path =
+                        Some(args.next().ok_or("--calling-order requires a file")?);
+                }
+                "--filter" => {
+                    filter = Some(args.next().ok_or("--filter requires a preset name")?);
+                }
+                flag if flag.starts_with("--") => {
+                    return Err(format!("unrecognized option '{}'", flag));
+                }
+                _ => paths.push(arg),
+            }
+        }
+
+        if paths.len() != 2 {
+            return Err("expected an input file and an output file".to_string());
+        }
+
+        let output_file_path = paths.pop().unwrap_or_default();
+        let input_file_path = paths.pop().unwrap_or_default();
+
+        Ok(Args {
+            input_file_path,
+            output_file_path,
+            include_vacant,
+            parse_mode,
+            as_of: as_of.unwrap_or_else(Date::today),
+            tenure_buckets,
+            calling_order_file_path,
+            filter,
+        })
+    }
+}
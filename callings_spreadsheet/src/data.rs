@@ -1,73 +1,77 @@
-#![allow(dead_code)]
-
+use lcr_core::date::{Date, Tenure};
 use std::fmt::Display;
 
-#[derive(Copy, Clone, Debug)]
-pub struct Date {
-    pub year: u16,
-    pub month: u8,
-    pub day: u8,
-}
-
-impl Display for Date {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
-    }
-}
-
-#[derive(Debug)]
-pub struct MemberWithCalling<'a> {
-    pub given_names: &'a str,
-    pub last_name: &'a str,
-    pub email: Option<&'a str>,
-    pub phone: Option<&'a str>,
-
-    pub held_calling_since: Date,
-    pub set_apart: bool,
-}
-
-#[derive(Debug)]
-pub struct Calling<'a> {
-    pub name: &'a str,
-    pub member: Option<MemberWithCalling<'a>>,
-}
-
-#[derive(Debug)]
-pub struct Organization<'a> {
-    pub name: &'a str,
-    pub children: Vec<Organization<'a>>,
-    pub callings: Vec<Calling<'a>>,
-}
-
 #[derive(Debug)]
 pub struct CsvRecord<'a> {
     pub member_last_name: &'a str,
     pub member_given_names: &'a str,
+    pub member_suffix: Option<&'a str>,
     pub calling: &'a str,
-    pub sub_sub_organization: &'a str,
-    pub sub_organization: &'a str,
     pub organization: &'a str,
-    pub held_calling_since: Date,
-    pub set_apart: bool,
+    pub organization_path: String,
+    pub held_calling_since: Option<Date>,
+    pub tenure: Option<Tenure>,
+    pub set_apart: Option<bool>,
     pub member_email: Option<&'a str>,
     pub member_phone: Option<&'a str>,
+    pub status: &'a str,
+    // Adds a Tenure Bucket column
+    pub with_buckets: bool,
+}
+
+impl CsvRecord<'_> {
+    pub fn headings(with_buckets: bool) -> String {
+        let mut headings = "Member Last Name, Member Given Names,Member Suffix,Calling,Organization,Organization Path,Held Calling Since,Tenure (Months),Tenure,Set Apart,Member Email,Member Phone,Status".to_string();
+
+        if with_buckets {
+            headings.push_str(",Tenure Bucket");
+        }
+
+        headings
+    }
 }
 
 impl Display for CsvRecord<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         write!(
             f,
-            "\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",{},{},\"{}\",\"{}\"",
+            "\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",\"{}\",{},{},\"{}\",{},\"{}\",\"{}\",\"{}\"",
             self.member_last_name,
             self.member_given_names,
+            self.member_suffix.unwrap_or(""),
             self.calling,
-            self.sub_sub_organization,
-            self.sub_organization,
             self.organization,
-            self.held_calling_since,
-            self.set_apart,
+            self.organization_path,
+            self.held_calling_since
+                .map(|d| d.to_string())
+                .unwrap_or_default(),
+            self.tenure
+                .map(|t| t.total_months().to_string())
+                .unwrap_or_default(),
+            self.tenure.map(|t| t.to_string()).unwrap_or_default(),
+            self.set_apart.map(|s| s.to_string()).unwrap_or_default(),
             self.member_email.unwrap_or(""),
-            self.member_phone.unwrap_or("")
-        )
+            self.member_phone.unwrap_or(""),
+            self.status
+        )?;
+
+        if self.with_buckets {
+            write!(
+                f,
+                ",\"{}\"",
+                self.tenure.map(tenure_bucket).unwrap_or_default()
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+fn tenure_bucket(tenure: Tenure) -> &'static str {
+    match tenure.years {
+        0 => "<1y",
+        1..=2 => "1-3y",
+        3..=4 => "3-5y",
+        _ => "5y+",
     }
 }
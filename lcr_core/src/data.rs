@@ -0,0 +1,65 @@
+use crate::date::Date;
+use crate::name::MemberName;
+
+#[derive(Debug, Clone)]
+pub struct MemberWithCalling {
+    pub id: Option<u64>,
+    pub legacy_cmis_id: Option<u64>,
+    pub name: MemberName,
+    pub email: Option<String>,
+    pub phone: Option<String>,
+    pub held_calling_since: Date,
+    pub set_apart: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Calling {
+    pub position_id: Option<u64>,
+    pub position_type_id: Option<u64>,
+    pub name: String,
+    pub display_sequence: Option<i64>,
+    pub member: Option<MemberWithCalling>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Organization {
+    pub id: Option<u64>,
+    pub type_ids: Vec<u64>,
+    pub name: String,
+    pub display_sequence: Option<i64>,
+    pub children: Vec<Organization>,
+    pub callings: Vec<Calling>,
+}
+
+impl Organization {
+    pub fn has_filled_callings(&self) -> bool {
+        self.callings.iter().any(|c| c.member.is_some())
+            || self.children.iter().any(|c| c.has_filled_callings())
+    }
+
+    // Returns every calling in this org and its descendants, each paired with the names of the
+    // orgs leading to it (starting with this org's name)
+    pub fn callings_with_paths(&self) -> Vec<(Vec<&str>, &Calling)> {
+        let mut callings = Vec::new();
+        self.collect_callings_with_paths(&mut Vec::new(), &mut callings);
+        callings
+    }
+
+    fn collect_callings_with_paths<'a>(
+        &'a self,
+        path: &mut Vec<&'a str>,
+        callings: &mut Vec<(Vec<&'a str>, &'a Calling)>,
+    ) {
+        path.push(&self.name);
+
+        for calling in &self.callings {
+            callings.push((path.clone(), calling));
+        }
+
+        for child in &self.children {
+            child.collect_callings_with_paths(path, callings);
+        }
+
+        path.pop();
+    }
+}
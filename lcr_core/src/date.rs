@@ -0,0 +1,320 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+// e.g. "2023, March 1st"
+pub const LONG_FORMAT: &str = "%Y, %B %-d%o";
+
+// e.g. "2023-03-01"
+pub const ISO_FORMAT: &str = "%Y-%m-%d";
+
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DateError {
+    InvalidFormat(String),
+    InvalidMonth(u8),
+    InvalidDay { day: u8, month: u8 },
+}
+
+impl std::error::Error for DateError {}
+
+impl fmt::Display for DateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DateError::InvalidFormat(date) => write!(f, "Invalid date format: {}", date),
+            DateError::InvalidMonth(month) => write!(f, "Invalid month: {}", month),
+            DateError::InvalidDay { day, month } => {
+                write!(f, "Invalid day: {} for month {}", day, month)
+            }
+        }
+    }
+}
+
+// Fields are private so that a `Date` is always a real calendar date. Field order matters for the
+// derived `Ord`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+impl Date {
+    pub fn new(year: u16, month: u8, day: u8) -> Result<Date, DateError> {
+        if !(1..=12).contains(&month) {
+            return Err(DateError::InvalidMonth(month));
+        }
+
+        if day == 0 || day > days_in_month(year, month) {
+            return Err(DateError::InvalidDay { day, month });
+        }
+
+        Ok(Date { year, month, day })
+    }
+
+    // Parses the "20230301" format LCR uses for `activeDate`
+    pub fn from_yyyymmdd(date: &str) -> Result<Date, DateError> {
+        if date.len() != 8 || !date.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(DateError::InvalidFormat(date.to_string()));
+        }
+
+        let invalid = |_| DateError::InvalidFormat(date.to_string());
+
+        Date::new(
+            date[0..4].parse().map_err(invalid)?,
+            date[4..6].parse().map_err(invalid)?,
+            date[6..8].parse().map_err(invalid)?,
+        )
+    }
+
+    // Today's date in UTC
+    pub fn today() -> Date {
+        let days_since_epoch = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() / 86400)
+            .unwrap_or(0);
+
+        Date::from_days_since_epoch(days_since_epoch as i64)
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    pub fn month_name(&self) -> &'static str {
+        MONTH_NAMES[self.month as usize - 1]
+    }
+
+    // Negative if `other` is before this date
+    pub fn days_until(&self, other: Date) -> i64 {
+        other.days_since_epoch() - self.days_since_epoch()
+    }
+
+    // The time from this date until `as_of`, or zero if `as_of` is before this date
+    pub fn tenure_until(&self, as_of: Date) -> Tenure {
+        if as_of <= *self {
+            return Tenure::default();
+        }
+
+        let mut years = as_of.year as i32 - self.year as i32;
+        let mut months = as_of.month as i32 - self.month as i32;
+        let mut days = as_of.day as i32 - self.day as i32;
+
+        if days < 0 {
+            months -= 1;
+
+            let (prev_month_year, prev_month) = if as_of.month == 1 {
+                (as_of.year - 1, 12)
+            } else {
+                (as_of.year, as_of.month - 1)
+            };
+            let days_in_prev_month = days_in_month(prev_month_year, prev_month) as i32;
+
+            // Count from the same day in the previous month, or its last day if the start day
+            // doesn't exist in that month (e.g. Jan 31 -> Feb 28)
+            days =
+                days_in_prev_month - (self.day as i32).min(days_in_prev_month) + as_of.day as i32;
+        }
+
+        if months < 0 {
+            years -= 1;
+            months += 12;
+        }
+
+        Tenure {
+            years: years as u32,
+            months: months as u32,
+            days: days as u32,
+        }
+    }
+
+    // Formats the date using a pattern made up of the following specifiers (anything else is
+    // copied as-is):
+    //
+    //   %Y  year              2023
+    //   %m  month             03
+    //   %-m month, unpadded   3
+    //   %B  month name        March
+    //   %b  short month name  Mar
+    //   %d  day               01
+    //   %-d day, unpadded     1
+    //   %o  ordinal suffix    st (as in 1st)
+    //   %%  a literal %
+    pub fn format(&self, pattern: &str) -> String {
+        let mut formatted = String::new();
+        let mut chars = pattern.chars();
+
+        while let Some(c) = chars.next() {
+            if c != '%' {
+                formatted.push(c);
+                continue;
+            }
+
+            match chars.next() {
+                Some('Y') => formatted.push_str(&format!("{:04}", self.year)),
+                Some('m') => formatted.push_str(&format!("{:02}", self.month)),
+                Some('B') => formatted.push_str(self.month_name()),
+                Some('b') => formatted.push_str(&self.month_name()[..3]),
+                Some('d') => formatted.push_str(&format!("{:02}", self.day)),
+                Some('o') => formatted.push_str(ordinal_suffix(self.day)),
+                Some('%') => formatted.push('%'),
+                Some('-') => match chars.next() {
+                    Some('m') => formatted.push_str(&self.month.to_string()),
+                    Some('d') => formatted.push_str(&self.day.to_string()),
+                    Some(other) => {
+                        formatted.push_str("%-");
+                        formatted.push(other);
+                    }
+                    None => formatted.push_str("%-"),
+                },
+                Some(other) => {
+                    formatted.push('%');
+                    formatted.push(other);
+                }
+                None => formatted.push('%'),
+            }
+        }
+
+        formatted
+    }
+
+    pub fn to_long_string(&self) -> String {
+        self.format(LONG_FORMAT)
+    }
+
+    fn days_since_epoch(&self) -> i64 {
+        // See http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+        let month = self.month as i64;
+        let year = self.year as i64 - if month <= 2 { 1 } else { 0 };
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+
+        era * 146097 + day_of_era - 719468
+    }
+
+    fn from_days_since_epoch(days: i64) -> Date {
+        // See http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+        let days = days + 719468;
+        let era = days.div_euclid(146097);
+        let day_of_era = days - era * 146097;
+        let year_of_era =
+            (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let shifted_month = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+        let month = if shifted_month < 10 {
+            shifted_month + 3
+        } else {
+            shifted_month - 9
+        };
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+        Date {
+            year: year as u16,
+            month: month as u8,
+            day: day as u8,
+        }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.format(ISO_FORMAT))
+    }
+}
+
+// Parses dates written like "2023-03-01"
+impl FromStr for Date {
+    type Err = DateError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || DateError::InvalidFormat(s.to_string());
+
+        let mut parts = s.trim().splitn(3, '-');
+        let year = parts.next().ok_or_else(invalid)?;
+        let month = parts.next().ok_or_else(invalid)?;
+        let day = parts.next().ok_or_else(invalid)?;
+
+        if year.len() != 4 {
+            return Err(invalid());
+        }
+
+        Date::new(
+            year.parse().map_err(|_| invalid())?,
+            month.parse().map_err(|_| invalid())?,
+            day.parse().map_err(|_| invalid())?,
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Tenure {
+    pub years: u32,
+    pub months: u32,
+    pub days: u32,
+}
+
+impl Tenure {
+    pub fn total_months(&self) -> u32 {
+        self.years * 12 + self.months
+    }
+}
+
+// e.g. "3y 4m", "4m" or "12d"
+impl fmt::Display for Tenure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.years > 0 {
+            write!(f, "{}y {}m", self.years, self.months)
+        } else if self.months > 0 {
+            write!(f, "{}m", self.months)
+        } else {
+            write!(f, "{}d", self.days)
+        }
+    }
+}
+
+fn is_leap_year(year: u16) -> bool {
+    year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400))
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+fn ordinal_suffix(day: u8) -> &'static str {
+    match day {
+        1 | 21 | 31 => "st",
+        2 | 22 => "nd",
+        3 | 23 => "rd",
+        _ => "th",
+    }
+}
@@ -0,0 +1,113 @@
+use crate::data::{Calling, Organization};
+use crate::date::Date;
+use crate::order::NamePattern;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+
+// The file named filters are loaded from, in the current directory
+pub const PRESETS_FILE: &str = "filters.json";
+
+#[derive(Debug)]
+pub enum FilterError {
+    InvalidPresets(serde_json::Error),
+    UnknownPreset(String),
+}
+
+impl std::error::Error for FilterError {}
+
+impl fmt::Display for FilterError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FilterError::InvalidPresets(err) => write!(f, "Invalid filter presets: {}", err),
+            FilterError::UnknownPreset(name) => write!(f, "No filter preset named '{}'", name),
+        }
+    }
+}
+
+// Matches a calling if every condition that is set matches
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct FilterRule {
+    // Matches the calling's org or any org it's inside of
+    pub org: Option<NamePattern>,
+    pub calling: Option<NamePattern>,
+    pub vacant: Option<bool>,
+    pub set_apart: Option<bool>,
+    pub min_tenure_months: Option<u32>,
+    pub max_tenure_months: Option<u32>,
+}
+
+impl FilterRule {
+    // `path` is the names of the orgs leading to the calling. Tenure is measured up to `as_of`.
+    pub fn matches(&self, path: &[&str], calling: &Calling, as_of: Date) -> bool {
+        let tenure_months = calling
+            .member
+            .as_ref()
+            .map(|m| m.held_calling_since.tenure_until(as_of).total_months());
+
+        self.org
+            .as_ref()
+            .is_none_or(|org| path.iter().any(|name| org.matches(name)))
+            && self
+                .calling
+                .as_ref()
+                .is_none_or(|c| c.matches(&calling.name))
+            && self.vacant.is_none_or(|v| v == calling.member.is_none())
+            && self
+                .set_apart
+                .is_none_or(|s| calling.member.as_ref().is_some_and(|m| m.set_apart == s))
+            && self
+                .min_tenure_months
+                .is_none_or(|min| tenure_months.is_some_and(|t| t >= min))
+            && self
+                .max_tenure_months
+                .is_none_or(|max| tenure_months.is_some_and(|t| t <= max))
+    }
+}
+
+// Keeps the callings that match any `include` rule (or all of them, if there are none) and don't
+// match any `exclude` rule
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct Filter {
+    pub include: Vec<FilterRule>,
+    pub exclude: Vec<FilterRule>,
+}
+
+impl Filter {
+    // Looks up a named filter in the contents of a presets file, which maps names to filters
+    pub fn preset(presets: &str, name: &str) -> Result<Filter, FilterError> {
+        let mut presets: HashMap<String, Filter> =
+            serde_json::from_str(presets).map_err(FilterError::InvalidPresets)?;
+
+        presets
+            .remove(name)
+            .ok_or_else(|| FilterError::UnknownPreset(name.to_string()))
+    }
+
+    pub fn allows(&self, path: &[&str], calling: &Calling, as_of: Date) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|r| r.matches(path, calling, as_of)))
+            && !self.exclude.iter().any(|r| r.matches(path, calling, as_of))
+    }
+
+    // Removes the callings the filter doesn't allow from the org and its descendants. Orgs are
+    // kept even if they end up with no callings.
+    pub fn apply(&self, org: &mut Organization, as_of: Date) {
+        self.apply_with_path(org, &mut Vec::new(), as_of);
+    }
+
+    fn apply_with_path(&self, org: &mut Organization, path: &mut Vec<String>, as_of: Date) {
+        path.push(org.name.clone());
+
+        let path_names = path.iter().map(String::as_str).collect::<Vec<_>>();
+        org.callings
+            .retain(|calling| self.allows(&path_names, calling, as_of));
+
+        for child in &mut org.children {
+            self.apply_with_path(child, path, as_of);
+        }
+
+        path.pop();
+    }
+}
@@ -0,0 +1,37 @@
+use serde::Deserialize;
+
+// Mirrors the response from https://lcr.churchofjesuschrist.org/api/orgs/sub-orgs-with-callings.
+// Fields that LCR doesn't always send are optional so that older exports still deserialize.
+
+// The callings can be read as something other than `LcrCalling` (e.g. raw JSON) so that a bad
+// calling doesn't have to fail the whole file
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LcrOrg<C = LcrCalling> {
+    pub sub_org_id: Option<u64>,
+    pub parent_sub_org_id: Option<u64>,
+    pub name: String,
+    #[serde(default)]
+    pub org_type_ids: Vec<u64>,
+    pub first_type_id: Option<u64>,
+    pub display_sequence: Option<i64>,
+    pub children: Vec<LcrOrg<C>>,
+    pub callings: Vec<C>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LcrCalling {
+    pub sub_org_id: Option<u64>,
+    pub position_id: Option<u64>,
+    pub position_type_id: Option<u64>,
+    pub position: String,
+    pub member_id: Option<u64>,
+    pub legacy_cmis_id: Option<u64>,
+    pub member_name: Option<String>,
+    pub member_email: Option<String>,
+    pub member_phone: Option<String>,
+    pub active_date: Option<String>,
+    pub set_apart: Option<bool>,
+    pub display_sequence: Option<i64>,
+}
@@ -0,0 +1,17 @@
+pub mod data;
+pub mod date;
+pub mod filter;
+pub mod lcr;
+pub mod name;
+pub mod order;
+pub mod parse;
+
+pub use data::{Calling, MemberWithCalling, Organization};
+pub use date::{Date, DateError, Tenure};
+pub use filter::{Filter, FilterError, FilterRule};
+pub use name::{MemberName, NameError};
+pub use order::{NamePattern, OrderingRules};
+pub use parse::{
+    ParseError, ParseMode, ParseWarning, ParseWarningKind, Parsed, RecordLocation,
+    lcr_orgs_from_str, orgs_from_lcr_data,
+};
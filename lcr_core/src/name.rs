@@ -0,0 +1,155 @@
+use std::fmt;
+
+// "V" and "VI" are left out since they are far more likely to be middle initials
+const SUFFIXES: [&str; 6] = ["jr", "sr", "ii", "iii", "iv", "esq"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NameError {
+    Empty,
+    OnlySuffix(String),
+}
+
+impl std::error::Error for NameError {}
+
+impl fmt::Display for NameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NameError::Empty => write!(f, "Member name is empty"),
+            NameError::OnlySuffix(name) => {
+                write!(f, "Member name contains only a suffix: {}", name)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemberName {
+    pub last_name: String,
+    pub given_names: String,
+    pub suffix: Option<String>,
+}
+
+impl MemberName {
+    // LCR formats names as "Last, Given Names", but suffixes show up in several places
+    // ("Smith, Jr., John", "Smith Jr., John", "Smith, John Jr.") and some records have no comma
+    // at all
+    pub fn parse(member_name: &str) -> Result<MemberName, NameError> {
+        let mut parts = member_name
+            .split(',')
+            .map(|part| part.split_whitespace().collect::<Vec<_>>())
+            .filter(|words| !words.is_empty())
+            .collect::<Vec<_>>();
+
+        if parts.is_empty() {
+            return Err(NameError::Empty);
+        }
+
+        // Checked on the raw name, since "Smith," has an empty part after the comma
+        let has_comma = member_name.contains(',');
+        let mut suffix = None;
+
+        if let Some(i) = (1..parts.len()).find(|&i| parts[i].len() == 1 && is_suffix(parts[i][0])) {
+            suffix = Some(parts.remove(i)[0].to_string());
+        }
+
+        if suffix.is_none() {
+            for part in parts.iter_mut() {
+                if part.len() > 1 && is_suffix(part[part.len() - 1]) {
+                    suffix = part.pop().map(|s| s.to_string());
+                    break;
+                }
+            }
+        }
+
+        let name = if parts.len() == 1 {
+            let mut words = parts.remove(0);
+
+            if words.len() == 1 && is_suffix(words[0]) {
+                return Err(NameError::OnlySuffix(member_name.trim().to_string()));
+            }
+
+            if has_comma {
+                // Everything other than the suffix came before the comma, e.g. "Smith, Jr."
+                return Ok(MemberName {
+                    last_name: words.join(" "),
+                    given_names: String::new(),
+                    suffix,
+                });
+            }
+
+            // Without a comma, assume the name is written "Given Names Last"
+            let last_name = if words.len() > 1 {
+                words.pop().unwrap_or_default()
+            } else {
+                ""
+            };
+
+            MemberName {
+                last_name: last_name.to_string(),
+                given_names: words.join(" "),
+                suffix,
+            }
+        } else {
+            let last_name = parts.remove(0).join(" ");
+            let given_names = parts
+                .iter()
+                .map(|words| words.join(" "))
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            MemberName {
+                last_name,
+                given_names,
+                suffix,
+            }
+        };
+
+        Ok(name)
+    }
+
+    // The name the way someone would say it, e.g. "John Smith Jr."
+    pub fn display_name(&self) -> String {
+        let mut name = [self.given_names.as_str(), self.last_name.as_str()]
+            .iter()
+            .filter(|s| !s.is_empty())
+            .copied()
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        if let Some(suffix) = &self.suffix {
+            name.push(' ');
+            name.push_str(suffix);
+        }
+
+        name
+    }
+
+    // The name the way LCR lists it, e.g. "Smith, John Jr."
+    pub fn last_name_first(&self) -> String {
+        let mut name = if self.last_name.is_empty() {
+            self.given_names.clone()
+        } else if self.given_names.is_empty() {
+            self.last_name.clone()
+        } else {
+            format!("{}, {}", self.last_name, self.given_names)
+        };
+
+        if let Some(suffix) = &self.suffix {
+            name.push(' ');
+            name.push_str(suffix);
+        }
+
+        name
+    }
+}
+
+impl fmt::Display for MemberName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.display_name())
+    }
+}
+
+fn is_suffix(word: &str) -> bool {
+    let word = word.trim_end_matches('.').to_lowercase();
+    SUFFIXES.contains(&word.as_str())
+}
@@ -0,0 +1,126 @@
+use crate::data::Organization;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+// Matches a calling or org name, either exactly (e.g. "Bishop") or, when written between slashes,
+// with a regex (e.g. "/Counselor$/")
+#[derive(Debug, Clone)]
+pub enum NamePattern {
+    Exact(String),
+    Regex(Regex),
+}
+
+impl NamePattern {
+    pub fn matches(&self, name: &str) -> bool {
+        match self {
+            NamePattern::Exact(exact) => exact == name,
+            NamePattern::Regex(regex) => regex.is_match(name),
+        }
+    }
+}
+
+impl FromStr for NamePattern {
+    type Err = regex::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.strip_prefix('/').and_then(|s| s.strip_suffix('/')) {
+            Some(regex) => Ok(NamePattern::Regex(Regex::new(regex)?)),
+            None => Ok(NamePattern::Exact(s.to_string())),
+        }
+    }
+}
+
+impl fmt::Display for NamePattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NamePattern::Exact(exact) => write!(f, "{}", exact),
+            NamePattern::Regex(regex) => write!(f, "/{}/", regex.as_str()),
+        }
+    }
+}
+
+impl Serialize for NamePattern {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for NamePattern {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse()
+            .map_err(|err| serde::de::Error::custom(format!("invalid pattern '{}': {}", s, err)))
+    }
+}
+
+// How the callings and sub-orgs inside each org are ordered. Each rule only breaks ties left by
+// the ones before it:
+//
+//   1. Names matching an earlier pattern in the priority list come first. The list in `orgs` for
+//      the org (or its nearest ancestor with one) is used, otherwise `priority`.
+//   2. If `display_sequence` is set, LCR's display sequence, with anything missing one last.
+//   3. If `alphabetical` is set, by name.
+//   4. The order LCR returned them in.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct OrderingRules {
+    pub priority: Vec<NamePattern>,
+    pub orgs: HashMap<String, Vec<NamePattern>>,
+    pub display_sequence: bool,
+    pub alphabetical: bool,
+}
+
+impl OrderingRules {
+    // Sorts the org's callings and sub-orgs, and theirs, all the way down
+    pub fn sort(&self, org: &mut Organization) {
+        self.sort_with_priority(org, &self.priority);
+    }
+
+    // Sorts a list of top-level orgs by `priority`, leaving what's inside them alone. They usually
+    // come out of a map with no LCR order to keep, so ties always fall back to display sequence and
+    // then name to keep the order the same from run to run.
+    pub fn sort_orgs(&self, orgs: &mut [Organization]) {
+        orgs.sort_by_cached_key(|org| {
+            (
+                self.sort_key(&org.name, org.display_sequence, &self.priority),
+                org.display_sequence.is_none(),
+                org.display_sequence,
+                org.name.clone(),
+            )
+        });
+    }
+
+    fn sort_with_priority(&self, org: &mut Organization, inherited: &[NamePattern]) {
+        let priority = self.orgs.get(&org.name).map_or(inherited, Vec::as_slice);
+
+        org.callings
+            .sort_by_cached_key(|c| self.sort_key(&c.name, c.display_sequence, priority));
+        org.children
+            .sort_by_cached_key(|c| self.sort_key(&c.name, c.display_sequence, priority));
+
+        for child in &mut org.children {
+            self.sort_with_priority(child, priority);
+        }
+    }
+
+    fn sort_key(
+        &self,
+        name: &str,
+        display_sequence: Option<i64>,
+        priority: &[NamePattern],
+    ) -> (usize, Option<(bool, i64)>, Option<String>) {
+        let rank = priority
+            .iter()
+            .position(|p| p.matches(name))
+            .unwrap_or(priority.len());
+        let display_sequence = self
+            .display_sequence
+            .then(|| (display_sequence.is_none(), display_sequence.unwrap_or(0)));
+        let name = self.alphabetical.then(|| name.to_lowercase());
+
+        (rank, display_sequence, name)
+    }
+}
@@ -0,0 +1,492 @@
+use crate::data::{Calling, MemberWithCalling, Organization};
+use crate::date::{Date, DateError};
+use crate::lcr::{LcrCalling, LcrOrg};
+use crate::name::{MemberName, NameError};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use serde_path_to_error::{Path, Segment};
+use std::collections::HashMap;
+use std::fmt;
+
+// Where in the LCR export a problem was found, e.g. `[3].children[1].callings[4].activeDate`,
+// along with the names of the orgs and the position at that spot
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordLocation {
+    pub json_path: String,
+    pub org_path: Vec<String>,
+    pub position: Option<String>,
+}
+
+impl fmt::Display for RecordLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut names = self.org_path.join(" > ");
+
+        if let Some(position) = &self.position {
+            if !names.is_empty() {
+                names.push_str(", ");
+            }
+            names.push_str(&format!("'{}'", position));
+        }
+
+        if names.is_empty() {
+            write!(f, "{}", self.json_path)
+        } else {
+            write!(f, "{} at {}", names, self.json_path)
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ParseError {
+    JsonError {
+        error: serde_json::Error,
+        location: Option<RecordLocation>,
+    },
+    InvalidRecord {
+        location: RecordLocation,
+        message: String,
+    },
+}
+
+impl std::error::Error for ParseError {}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::JsonError {
+                error,
+                location: Some(location),
+            } => write!(f, "JSON error in {}: {}", location, error),
+            ParseError::JsonError {
+                error,
+                location: None,
+            } => write!(f, "JSON error: {}", error),
+            ParseError::InvalidRecord { location, message } => {
+                write!(f, "Invalid record in {}: {}", location, message)
+            }
+        }
+    }
+}
+
+impl From<serde_json::Error> for ParseError {
+    fn from(err: serde_json::Error) -> Self {
+        ParseError::JsonError {
+            error: err,
+            location: None,
+        }
+    }
+}
+
+pub fn lcr_orgs_from_str(lcr_data: &str) -> Result<Vec<LcrOrg>, ParseError> {
+    deserialize_orgs(lcr_data)
+}
+
+fn deserialize_orgs<C: DeserializeOwned>(lcr_data: &str) -> Result<Vec<LcrOrg<C>>, ParseError> {
+    let deserializer = &mut serde_json::Deserializer::from_str(lcr_data);
+
+    serde_path_to_error::deserialize(deserializer).map_err(|err| {
+        let location = json_error_location(lcr_data, err.path());
+
+        ParseError::JsonError {
+            error: err.into_inner(),
+            location,
+        }
+    })
+}
+
+// serde only knows the path to the value it failed on, so walk the raw JSON along that path to
+// pick up the names of the orgs and the position it belongs to
+fn json_error_location(lcr_data: &str, path: &Path) -> Option<RecordLocation> {
+    path.iter().next()?;
+
+    let mut org_path = Vec::new();
+    let mut position = None;
+
+    // If the JSON itself is malformed, only the path is available
+    if let Ok(root) = serde_json::from_str::<Value>(lcr_data) {
+        let mut value = &root;
+
+        for segment in path.iter() {
+            let next = match segment {
+                Segment::Seq { index } => value.get(index),
+                Segment::Map { key } => value.get(key),
+                _ => None,
+            };
+
+            let Some(next) = next else {
+                break;
+            };
+            value = next;
+
+            if let Some(p) = value.get("position").and_then(Value::as_str) {
+                position = Some(p.to_string());
+            } else if let Some(name) = value.get("name").and_then(Value::as_str) {
+                org_path.push(name.to_string());
+            }
+        }
+    }
+
+    Some(RecordLocation {
+        json_path: path.to_string(),
+        org_path,
+        position,
+    })
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseMode {
+    // Any problem with a record fails the parse
+    Strict,
+    // Records with problems are defaulted or skipped and reported as warnings
+    Lenient,
+}
+
+#[derive(Debug)]
+pub enum ParseWarningKind {
+    UnparseableMemberName {
+        member_name: String,
+        error: NameError,
+    },
+    MissingSetApart,
+    MissingActiveDate,
+    InvalidActiveDate(DateError),
+    // A field with the wrong type of value, e.g. a number for 'activeDate'
+    InvalidField {
+        field: &'static str,
+        value: Value,
+    },
+}
+
+impl ParseWarningKind {
+    fn recovery(&self) -> &'static str {
+        match self {
+            ParseWarningKind::UnparseableMemberName { .. } => "using the name as-is",
+            ParseWarningKind::MissingSetApart => "assuming not set apart",
+            ParseWarningKind::MissingActiveDate | ParseWarningKind::InvalidActiveDate(_) => {
+                "skipping the calling"
+            }
+            ParseWarningKind::InvalidField { field, .. } => match *field {
+                "position" | "memberName" | "activeDate" => "skipping the calling",
+                "setApart" => "assuming not set apart",
+                _ => "leaving it out",
+            },
+        }
+    }
+}
+
+impl fmt::Display for ParseWarningKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseWarningKind::UnparseableMemberName { member_name, error } => {
+                write!(
+                    f,
+                    "Could not parse member name '{}': {}",
+                    member_name, error
+                )
+            }
+            ParseWarningKind::MissingSetApart => write!(f, "Missing or invalid 'setApart' field"),
+            ParseWarningKind::MissingActiveDate => write!(f, "Missing 'activeDate' field"),
+            ParseWarningKind::InvalidActiveDate(error) => write!(f, "{}", error),
+            ParseWarningKind::InvalidField { field, value } => {
+                write!(f, "Invalid '{}' field: {}", field, value)
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseWarning {
+    pub location: RecordLocation,
+    pub kind: ParseWarningKind,
+}
+
+impl fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {}; {}",
+            self.location,
+            self.kind,
+            self.kind.recovery()
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct Parsed {
+    pub orgs: HashMap<String, Organization>,
+    pub warnings: Vec<ParseWarning>,
+}
+
+pub fn orgs_from_lcr_data(lcr_data: &str, mode: ParseMode) -> Result<Parsed, ParseError> {
+    let mut parser = Parser {
+        mode,
+        json_path: Vec::new(),
+        org_path: Vec::new(),
+        warnings: Vec::new(),
+    };
+
+    // Strict mode deserializes the callings directly so that serde reports where a bad one is
+    let orgs = match mode {
+        ParseMode::Strict => parser.process_orgs(deserialize_orgs::<LcrCalling>(lcr_data)?)?,
+        ParseMode::Lenient => parser.process_orgs(deserialize_orgs::<Value>(lcr_data)?)?,
+    };
+
+    Ok(Parsed {
+        orgs,
+        warnings: parser.warnings,
+    })
+}
+
+struct Parser {
+    mode: ParseMode,
+    json_path: Vec<String>,
+    org_path: Vec<String>,
+    warnings: Vec<ParseWarning>,
+}
+
+// The type a calling is first read as: `LcrCalling` in strict mode, or raw JSON in lenient mode
+trait RawCalling {
+    fn into_lcr_calling(self, parser: &mut Parser) -> Result<Option<LcrCalling>, ParseError>;
+}
+
+impl RawCalling for LcrCalling {
+    fn into_lcr_calling(self, _: &mut Parser) -> Result<Option<LcrCalling>, ParseError> {
+        Ok(Some(self))
+    }
+}
+
+impl RawCalling for Value {
+    fn into_lcr_calling(self, parser: &mut Parser) -> Result<Option<LcrCalling>, ParseError> {
+        parser.lcr_calling(self)
+    }
+}
+
+// Whether a field's value has the type `LcrCalling` expects
+type FieldCheck = fn(&Value) -> bool;
+
+// The fields of `LcrCalling`, in the order they're checked when a calling fails to deserialize
+const CALLING_FIELDS: &[(&str, FieldCheck)] = &[
+    ("position", is::<String>),
+    ("memberName", is::<Option<String>>),
+    ("activeDate", is::<Option<String>>),
+    ("setApart", is::<Option<bool>>),
+    ("subOrgId", is::<Option<u64>>),
+    ("positionId", is::<Option<u64>>),
+    ("positionTypeId", is::<Option<u64>>),
+    ("memberId", is::<Option<u64>>),
+    ("legacyCmisId", is::<Option<u64>>),
+    ("memberEmail", is::<Option<String>>),
+    ("memberPhone", is::<Option<String>>),
+    ("displaySequence", is::<Option<i64>>),
+];
+
+fn is<T: DeserializeOwned>(value: &Value) -> bool {
+    T::deserialize(value).is_ok()
+}
+
+impl Parser {
+    fn process_orgs<C: RawCalling>(
+        &mut self,
+        lcr_orgs: Vec<LcrOrg<C>>,
+    ) -> Result<HashMap<String, Organization>, ParseError> {
+        let mut orgs = HashMap::new();
+
+        for (i, lcr_org) in lcr_orgs.into_iter().enumerate() {
+            self.json_path.push(format!("[{}]", i));
+            let org = self.process_org(lcr_org)?;
+            self.json_path.pop();
+
+            orgs.insert(org.name.clone(), org);
+        }
+
+        Ok(orgs)
+    }
+
+    fn process_org<C: RawCalling>(
+        &mut self,
+        lcr_org: LcrOrg<C>,
+    ) -> Result<Organization, ParseError> {
+        self.org_path.push(lcr_org.name.clone());
+
+        let mut children = Vec::new();
+        for (i, lcr_child) in lcr_org.children.into_iter().enumerate() {
+            self.json_path.push(format!(".children[{}]", i));
+            children.push(self.process_org(lcr_child)?);
+            self.json_path.pop();
+        }
+
+        let mut callings = Vec::new();
+        for (i, lcr_calling) in lcr_org.callings.into_iter().enumerate() {
+            self.json_path.push(format!(".callings[{}]", i));
+            let calling = match lcr_calling.into_lcr_calling(self)? {
+                Some(lcr_calling) => self.process_calling(lcr_calling)?,
+                None => None,
+            };
+            self.json_path.pop();
+
+            if let Some(calling) = calling {
+                callings.push(calling);
+            }
+        }
+
+        self.org_path.pop();
+
+        Ok(Organization {
+            id: lcr_org.sub_org_id,
+            type_ids: lcr_org.org_type_ids,
+            name: lcr_org.name,
+            display_sequence: lcr_org.display_sequence,
+            children,
+            callings,
+        })
+    }
+
+    // Returns `None` if the calling had to be skipped
+    fn process_calling(&mut self, lcr_calling: LcrCalling) -> Result<Option<Calling>, ParseError> {
+        let member = match &lcr_calling.member_name {
+            Some(member_name) => match self.process_member(member_name, &lcr_calling)? {
+                Some(member) => Some(member),
+                None => return Ok(None),
+            },
+            None => None,
+        };
+
+        Ok(Some(Calling {
+            position_id: lcr_calling.position_id,
+            position_type_id: lcr_calling.position_type_id,
+            name: lcr_calling.position,
+            display_sequence: lcr_calling.display_sequence,
+            member,
+        }))
+    }
+
+    fn process_member(
+        &mut self,
+        member_name: &str,
+        lcr_calling: &LcrCalling,
+    ) -> Result<Option<MemberWithCalling>, ParseError> {
+        let position = Some(lcr_calling.position.as_str());
+
+        let held_calling_since = match lcr_calling.active_date.as_deref() {
+            Some(active_date) => {
+                Date::from_yyyymmdd(active_date).map_err(ParseWarningKind::InvalidActiveDate)
+            }
+            None => Err(ParseWarningKind::MissingActiveDate),
+        };
+
+        let held_calling_since = match held_calling_since {
+            Ok(date) => date,
+            Err(kind) => {
+                self.warn(position, "activeDate", kind)?;
+                return Ok(None);
+            }
+        };
+
+        let set_apart = match lcr_calling.set_apart {
+            Some(set_apart) => set_apart,
+            None => {
+                self.warn(position, "setApart", ParseWarningKind::MissingSetApart)?;
+                false
+            }
+        };
+
+        let name = match MemberName::parse(member_name) {
+            Ok(name) => name,
+            Err(error) => {
+                self.warn(
+                    position,
+                    "memberName",
+                    ParseWarningKind::UnparseableMemberName {
+                        member_name: member_name.to_string(),
+                        error,
+                    },
+                )?;
+
+                MemberName {
+                    last_name: String::new(),
+                    given_names: member_name.trim().to_string(),
+                    suffix: None,
+                }
+            }
+        };
+
+        Ok(Some(MemberWithCalling {
+            id: lcr_calling.member_id,
+            legacy_cmis_id: lcr_calling.legacy_cmis_id,
+            name,
+            email: lcr_calling.member_email.clone(),
+            phone: lcr_calling.member_phone.clone(),
+            held_calling_since,
+            set_apart,
+        }))
+    }
+
+    // Types a calling read as raw JSON in lenient mode. If it doesn't deserialize, each field with
+    // the wrong type of value is warned about and then left out, defaulted, or causes the calling
+    // to be skipped.
+    fn lcr_calling(&mut self, mut calling: Value) -> Result<Option<LcrCalling>, ParseError> {
+        if let Ok(lcr_calling) = LcrCalling::deserialize(&calling) {
+            return Ok(Some(lcr_calling));
+        }
+
+        let position = calling
+            .get("position")
+            .and_then(Value::as_str)
+            .map(str::to_string);
+
+        for &(field, is_valid) in CALLING_FIELDS {
+            let value = calling.get(field).unwrap_or(&Value::Null);
+            if is_valid(value) {
+                continue;
+            }
+
+            let kind = ParseWarningKind::InvalidField {
+                field,
+                value: value.clone(),
+            };
+            self.warn(position.as_deref(), field, kind)?;
+
+            match field {
+                "position" | "memberName" | "activeDate" => return Ok(None),
+                "setApart" => calling[field] = Value::Bool(false),
+                _ => {
+                    if let Some(fields) = calling.as_object_mut() {
+                        fields.remove(field);
+                    }
+                }
+            }
+        }
+
+        Ok(Some(LcrCalling::deserialize(&calling)?))
+    }
+
+    // In strict mode, a warning fails the parse instead of being recorded
+    fn warn(
+        &mut self,
+        position: Option<&str>,
+        field: &str,
+        kind: ParseWarningKind,
+    ) -> Result<(), ParseError> {
+        let warning = ParseWarning {
+            location: RecordLocation {
+                json_path: format!("{}.{}", self.json_path.concat(), field),
+                org_path: self.org_path.clone(),
+                position: position.map(str::to_string),
+            },
+            kind,
+        };
+
+        match self.mode {
+            ParseMode::Strict => Err(ParseError::InvalidRecord {
+                message: warning.kind.to_string(),
+                location: warning.location,
+            }),
+            ParseMode::Lenient => {
+                self.warnings.push(warning);
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,74 @@
+use lcr_core::{Date, DateError, Tenure};
+
+fn date(s: &str) -> Date {
+    s.parse().unwrap()
+}
+
+#[test]
+fn validates_dates() {
+    assert!(Date::new(2024, 2, 29).is_ok());
+    assert_eq!(
+        Date::new(2023, 2, 29),
+        Err(DateError::InvalidDay { day: 29, month: 2 })
+    );
+    assert_eq!(Date::new(2023, 13, 1), Err(DateError::InvalidMonth(13)));
+    assert!(Date::from_yyyymmdd("20230301").is_ok());
+    assert!(Date::from_yyyymmdd("2023031").is_err());
+    assert!(Date::from_yyyymmdd("20231301").is_err());
+    assert!("2023-3-1".parse::<Date>().is_ok());
+    assert!("03/01/2023".parse::<Date>().is_err());
+}
+
+#[test]
+fn orders_dates() {
+    assert!(date("2022-12-31") < date("2023-01-01"));
+    assert!(date("2023-02-01") > date("2023-01-31"));
+    assert_eq!(date("2023-01-01").days_until(date("2024-01-01")), 365);
+    assert_eq!(date("2024-01-01").days_until(date("2025-01-01")), 366);
+    assert_eq!(date("2023-03-01").days_until(date("2023-02-28")), -1);
+}
+
+#[test]
+fn computes_tenure() {
+    assert_eq!(
+        date("2020-01-15").tenure_until(date("2023-05-20")),
+        Tenure {
+            years: 3,
+            months: 4,
+            days: 5
+        }
+    );
+    assert_eq!(
+        date("2023-01-31").tenure_until(date("2023-03-01")),
+        Tenure {
+            years: 0,
+            months: 1,
+            days: 1
+        }
+    );
+    assert_eq!(
+        date("2023-05-20").tenure_until(date("2020-01-15")),
+        Tenure::default()
+    );
+
+    let tenure = date("2020-01-15").tenure_until(date("2023-05-20"));
+    assert_eq!(tenure.total_months(), 40);
+    assert_eq!(tenure.to_string(), "3y 4m");
+}
+
+#[test]
+fn formats_dates() {
+    let d = date("2023-03-01");
+    assert_eq!(d.to_string(), "2023-03-01");
+    assert_eq!(d.to_long_string(), "2023, March 1st");
+    assert_eq!(d.format("%b %-d, %Y"), "Mar 1, 2023");
+    assert_eq!(d.format("%-m/%d 100%%"), "3/01 100%");
+    assert_eq!(date("2023-11-22").format("%B %-d%o"), "November 22nd");
+}
+
+#[test]
+fn today_is_a_valid_date() {
+    let today = Date::today();
+    assert!(today.year() >= 2024);
+    assert!(Date::new(today.year(), today.month(), today.day()).is_ok());
+}
@@ -0,0 +1,119 @@
+use lcr_core::{Filter, FilterError, ParseMode, orgs_from_lcr_data};
+
+const LCR_DATA: &str = r#"[
+    {
+        "name": "Primary",
+        "children": [
+            {
+                "name": "Primary Presidency",
+                "children": [],
+                "callings": [
+                    {
+                        "position": "Primary President",
+                        "memberName": "Jones, Mary Ann",
+                        "activeDate": "20201115",
+                        "setApart": true
+                    },
+                    {
+                        "position": "Primary Secretary",
+                        "memberName": "Smith, John",
+                        "activeDate": "20231001",
+                        "setApart": false
+                    }
+                ]
+            },
+            {
+                "name": "CTR 4",
+                "children": [],
+                "callings": [
+                    {
+                        "position": "Primary Teacher",
+                        "memberName": "Young, Brigham",
+                        "activeDate": "20220101",
+                        "setApart": true
+                    },
+                    { "position": "Primary Teacher", "memberName": null }
+                ]
+            }
+        ],
+        "callings": []
+    }
+]"#;
+
+const PRESETS: &str = r#"{
+    "leadership": {
+        "include": [{ "org": "/Presidency$/" }],
+        "exclude": [{ "calling": "/Secretary/" }]
+    },
+    "teachers": { "include": [{ "calling": "Primary Teacher", "vacant": false }] },
+    "not_set_apart": { "include": [{ "set_apart": false }] },
+    "new": { "include": [{ "max_tenure_months": 12 }] }
+}"#;
+
+fn filtered(preset: &str) -> Vec<(String, String)> {
+    let mut orgs = orgs_from_lcr_data(LCR_DATA, ParseMode::Strict)
+        .unwrap()
+        .orgs;
+    let primary = orgs.get_mut("Primary").unwrap();
+
+    Filter::preset(PRESETS, preset)
+        .unwrap()
+        .apply(primary, "2024-01-01".parse().unwrap());
+
+    primary
+        .callings_with_paths()
+        .into_iter()
+        .map(|(path, calling)| (path.last().unwrap().to_string(), calling.name.clone()))
+        .collect()
+}
+
+fn owned(callings: &[(&str, &str)]) -> Vec<(String, String)> {
+    callings
+        .iter()
+        .map(|(org, calling)| (org.to_string(), calling.to_string()))
+        .collect()
+}
+
+#[test]
+fn includes_by_org_and_excludes_by_calling() {
+    assert_eq!(
+        filtered("leadership"),
+        owned(&[("Primary Presidency", "Primary President")])
+    );
+}
+
+#[test]
+fn filters_by_member_attributes() {
+    assert_eq!(filtered("teachers"), owned(&[("CTR 4", "Primary Teacher")]));
+    assert_eq!(
+        filtered("not_set_apart"),
+        owned(&[("Primary Presidency", "Primary Secretary")])
+    );
+    assert_eq!(
+        filtered("new"),
+        owned(&[("Primary Presidency", "Primary Secretary")])
+    );
+}
+
+#[test]
+fn empty_filter_keeps_everything() {
+    let mut orgs = orgs_from_lcr_data(LCR_DATA, ParseMode::Strict)
+        .unwrap()
+        .orgs;
+    let primary = orgs.get_mut("Primary").unwrap();
+    Filter::default().apply(primary, "2024-01-01".parse().unwrap());
+
+    assert_eq!(primary.callings_with_paths().len(), 4);
+}
+
+#[test]
+fn reports_unknown_presets() {
+    assert!(matches!(
+        Filter::preset(PRESETS, "missing"),
+        Err(FilterError::UnknownPreset(name)) if name == "missing"
+    ));
+    assert!(matches!(
+        Filter::preset("[]", "leadership"),
+        Err(FilterError::InvalidPresets(_))
+    ));
+}
@@ -0,0 +1,82 @@
+use lcr_core::{MemberName, NameError};
+
+fn parse(name: &str) -> (String, String, Option<String>) {
+    let name = MemberName::parse(name).unwrap();
+    (name.last_name, name.given_names, name.suffix)
+}
+
+fn owned(last: &str, given: &str, suffix: Option<&str>) -> (String, String, Option<String>) {
+    (
+        last.to_string(),
+        given.to_string(),
+        suffix.map(|s| s.to_string()),
+    )
+}
+
+#[test]
+fn parses_last_name_first() {
+    assert_eq!(parse("Smith, John"), owned("Smith", "John", None));
+    assert_eq!(parse("Smith,John  Paul"), owned("Smith", "John Paul", None));
+    assert_eq!(
+        parse("  Van  Buren ,  Martin "),
+        owned("Van Buren", "Martin", None)
+    );
+}
+
+#[test]
+fn parses_suffixes() {
+    assert_eq!(
+        parse("Smith, Jr., John"),
+        owned("Smith", "John", Some("Jr."))
+    );
+    assert_eq!(
+        parse("Smith, John, III"),
+        owned("Smith", "John", Some("III"))
+    );
+    assert_eq!(
+        parse("Smith Jr., John"),
+        owned("Smith", "John", Some("Jr."))
+    );
+    assert_eq!(parse("Smith, John Sr"), owned("Smith", "John", Some("Sr")));
+    assert_eq!(parse("Smith, John V"), owned("Smith", "John V", None));
+}
+
+#[test]
+fn parses_names_without_given_names() {
+    assert_eq!(parse("Smith,"), owned("Smith", "", None));
+    assert_eq!(parse("Smith, Jr."), owned("Smith", "", Some("Jr.")));
+    assert_eq!(parse(" Van Buren , "), owned("Van Buren", "", None));
+}
+
+#[test]
+fn parses_names_without_commas() {
+    assert_eq!(parse("Madonna"), owned("", "Madonna", None));
+    assert_eq!(parse("John Smith"), owned("Smith", "John", None));
+}
+
+#[test]
+fn rejects_unparseable_names() {
+    assert_eq!(MemberName::parse(" , "), Err(NameError::Empty));
+    assert!(matches!(
+        MemberName::parse("Jr."),
+        Err(NameError::OnlySuffix(_))
+    ));
+}
+
+#[test]
+fn formats_names() {
+    let name = MemberName::parse("Smith, Jr., John").unwrap();
+    assert_eq!(name.display_name(), "John Smith Jr.");
+    assert_eq!(name.last_name_first(), "Smith, John Jr.");
+
+    let name = MemberName::parse("Smith, Jr.").unwrap();
+    assert_eq!(name.display_name(), "Smith Jr.");
+    assert_eq!(name.last_name_first(), "Smith Jr.");
+
+    let name = MemberName::parse("Smith,").unwrap();
+    assert_eq!(name.last_name_first(), "Smith");
+
+    let name = MemberName::parse("Madonna").unwrap();
+    assert_eq!(name.display_name(), "Madonna");
+    assert_eq!(name.last_name_first(), "Madonna");
+}
@@ -0,0 +1,146 @@
+use lcr_core::{Calling, OrderingRules, Organization};
+
+fn calling(name: &str, display_sequence: Option<i64>) -> Calling {
+    Calling {
+        position_id: None,
+        position_type_id: None,
+        name: name.to_string(),
+        display_sequence,
+        member: None,
+    }
+}
+
+fn org(name: &str, children: Vec<Organization>, callings: Vec<Calling>) -> Organization {
+    Organization {
+        id: None,
+        type_ids: Vec::new(),
+        name: name.to_string(),
+        display_sequence: None,
+        children,
+        callings,
+    }
+}
+
+fn rules(json: &str) -> OrderingRules {
+    serde_json::from_str(json).unwrap()
+}
+
+fn calling_names(org: &Organization) -> Vec<&str> {
+    org.callings.iter().map(|c| c.name.as_str()).collect()
+}
+
+fn presidency() -> Organization {
+    org(
+        "Primary Presidency",
+        Vec::new(),
+        vec![
+            calling("Primary Secretary", Some(4)),
+            calling("Primary Second Counselor", Some(3)),
+            calling("Primary President", None),
+            calling("Primary First Counselor", Some(2)),
+        ],
+    )
+}
+
+#[test]
+fn keeps_lcr_order_by_default() {
+    let mut org = presidency();
+    OrderingRules::default().sort(&mut org);
+
+    assert_eq!(
+        calling_names(&org),
+        vec![
+            "Primary Secretary",
+            "Primary Second Counselor",
+            "Primary President",
+            "Primary First Counselor",
+        ]
+    );
+}
+
+#[test]
+fn sorts_by_display_sequence_with_missing_last() {
+    let mut org = presidency();
+    rules(r#"{ "display_sequence": true }"#).sort(&mut org);
+
+    assert_eq!(
+        calling_names(&org),
+        vec![
+            "Primary First Counselor",
+            "Primary Second Counselor",
+            "Primary Secretary",
+            "Primary President",
+        ]
+    );
+}
+
+#[test]
+fn sorts_by_priority_then_fallbacks() {
+    let mut org = presidency();
+    rules(r#"{ "priority": ["Primary President", "/Counselor$/"], "alphabetical": true }"#)
+        .sort(&mut org);
+
+    assert_eq!(
+        calling_names(&org),
+        vec![
+            "Primary President",
+            "Primary First Counselor",
+            "Primary Second Counselor",
+            "Primary Secretary",
+        ]
+    );
+}
+
+#[test]
+fn uses_nearest_org_priority_list() {
+    let mut primary = org(
+        "Primary",
+        vec![org("Primary Classes", Vec::new(), Vec::new()), presidency()],
+        Vec::new(),
+    );
+    rules(
+        r#"{
+            "priority": ["/Presidency$/"],
+            "orgs": { "Primary Presidency": ["/Secretary/"] }
+        }"#,
+    )
+    .sort(&mut primary);
+
+    assert_eq!(primary.children[0].name, "Primary Presidency");
+    assert_eq!(calling_names(&primary.children[0])[0], "Primary Secretary");
+}
+
+#[test]
+fn sorts_top_level_orgs_by_priority_then_display_sequence_and_name() {
+    let mut orgs = vec![
+        org("Sunday School", Vec::new(), Vec::new()),
+        org("Primary", Vec::new(), Vec::new()),
+        Organization {
+            display_sequence: Some(2),
+            ..org("Relief Society", Vec::new(), Vec::new())
+        },
+        org("Bishopric", Vec::new(), Vec::new()),
+        Organization {
+            display_sequence: Some(1),
+            ..org("Elders Quorum", Vec::new(), Vec::new())
+        },
+    ];
+    rules(r#"{ "priority": ["Bishopric"] }"#).sort_orgs(&mut orgs);
+
+    assert_eq!(
+        orgs.iter().map(|o| o.name.as_str()).collect::<Vec<_>>(),
+        vec![
+            "Bishopric",
+            "Elders Quorum",
+            "Relief Society",
+            "Primary",
+            "Sunday School",
+        ]
+    );
+}
+
+#[test]
+fn rejects_invalid_regex() {
+    let err = serde_json::from_str::<OrderingRules>(r#"{ "priority": ["/(/"] }"#).unwrap_err();
+    assert!(err.to_string().contains("invalid pattern '/(/'"));
+}
@@ -0,0 +1,294 @@
+use lcr_core::{ParseError, ParseMode, ParseWarningKind, orgs_from_lcr_data};
+
+const LCR_DATA: &str = r#"[
+    {
+        "subOrgId": 1001,
+        "name": "Bishopric",
+        "orgTypeIds": [1179],
+        "children": [],
+        "callings": [
+            {
+                "positionId": 2001,
+                "position": "Bishop",
+                "memberId": 3001,
+                "legacyCmisId": 4001,
+                "displaySequence": 1,
+                "memberName": "Smith, John",
+                "activeDate": "20230301",
+                "setApart": true,
+                "memberEmail": "john@example.com",
+                "memberPhone": "555-555-5555"
+            },
+            {
+                "position": "Ward Clerk",
+                "memberName": null
+            }
+        ]
+    },
+    {
+        "name": "Primary",
+        "children": [
+            {
+                "name": "Primary Presidency",
+                "children": [],
+                "callings": [
+                    {
+                        "position": "Primary President",
+                        "memberName": "Jones,Mary Ann",
+                        "activeDate": "20201115",
+                        "setApart": false,
+                        "memberEmail": null,
+                        "memberPhone": null
+                    }
+                ]
+            }
+        ],
+        "callings": []
+    }
+]"#;
+
+#[test]
+fn parses_orgs_and_callings() {
+    let orgs = orgs_from_lcr_data(LCR_DATA, ParseMode::Strict)
+        .unwrap()
+        .orgs;
+    assert_eq!(orgs.len(), 2);
+
+    let bishopric = &orgs["Bishopric"];
+    assert_eq!(bishopric.id, Some(1001));
+    assert_eq!(bishopric.type_ids, vec![1179]);
+    assert_eq!(bishopric.callings.len(), 2);
+    assert_eq!(bishopric.callings[0].position_id, Some(2001));
+    assert_eq!(bishopric.callings[0].display_sequence, Some(1));
+    assert!(bishopric.callings[1].member.is_none());
+
+    let bishop = bishopric.callings[0].member.as_ref().unwrap();
+    assert_eq!(bishop.name.last_name, "Smith");
+    assert_eq!(bishop.name.given_names, "John");
+    assert_eq!(bishop.email.as_deref(), Some("john@example.com"));
+    assert_eq!(bishop.held_calling_since.to_string(), "2023-03-01");
+    assert!(bishop.set_apart);
+    assert_eq!(bishop.id, Some(3001));
+    assert_eq!(bishop.legacy_cmis_id, Some(4001));
+
+    let primary = &orgs["Primary"];
+    assert_eq!(primary.children.len(), 1);
+
+    let president = primary.children[0].callings[0].member.as_ref().unwrap();
+    assert_eq!(president.name.last_name, "Jones");
+    assert_eq!(president.name.given_names, "Mary Ann");
+    assert!(!president.set_apart);
+    assert!(president.phone.is_none());
+}
+
+#[test]
+fn rejects_missing_set_apart() {
+    let data = r#"[{"name": "Bishopric", "children": [], "callings": [
+        {"position": "Bishop", "memberName": "Smith, John", "activeDate": "20230301"}
+    ]}]"#;
+
+    assert!(matches!(
+        orgs_from_lcr_data(data, ParseMode::Strict),
+        Err(ParseError::InvalidRecord { .. })
+    ));
+}
+
+#[test]
+fn rejects_malformed_date() {
+    let data = r#"[{"name": "Bishopric", "children": [], "callings": [
+        {"position": "Bishop", "memberName": "Smith, John", "activeDate": "2023031", "setApart": true}
+    ]}]"#;
+
+    assert!(matches!(
+        orgs_from_lcr_data(data, ParseMode::Strict),
+        Err(ParseError::InvalidRecord { .. })
+    ));
+}
+
+#[test]
+fn json_errors_report_position() {
+    let data = "[{\"name\": \"Bishopric\", \"children\": [],\n\"callings\": 5}]";
+
+    match orgs_from_lcr_data(data, ParseMode::Strict) {
+        Err(ParseError::JsonError { error, location }) => {
+            assert_eq!(error.line(), 2);
+
+            let location = location.unwrap();
+            assert_eq!(location.json_path, "[0].callings");
+            assert_eq!(location.org_path, vec!["Bishopric"]);
+        }
+        other => panic!("expected JSON error, got {:?}", other.map(|_| ())),
+    }
+}
+
+#[test]
+fn collects_callings_at_any_depth() {
+    let data = r#"[{"name": "Primary", "callings": [], "children": [
+        {"name": "Classes", "callings": [], "children": [
+            {"name": "Valiant 9", "callings": [], "children": [
+                {"name": "Valiant 9 Boys", "children": [], "callings": [
+                    {"position": "Primary Teacher", "memberName": null}
+                ]}
+            ]}
+        ]}
+    ]}]"#;
+
+    let orgs = orgs_from_lcr_data(data, ParseMode::Strict).unwrap().orgs;
+    let callings = orgs["Primary"].callings_with_paths();
+
+    assert_eq!(callings.len(), 1);
+    assert_eq!(
+        callings[0].0,
+        vec!["Primary", "Classes", "Valiant 9", "Valiant 9 Boys"]
+    );
+    assert_eq!(callings[0].1.name, "Primary Teacher");
+    assert!(!orgs["Primary"].has_filled_callings());
+}
+
+#[test]
+fn unparseable_member_names_are_warnings() {
+    let data = r#"[{"name": "Bishopric", "children": [], "callings": [
+        {"position": "Bishop", "memberName": " , ", "activeDate": "20230301", "setApart": true}
+    ]}]"#;
+
+    let parsed = orgs_from_lcr_data(data, ParseMode::Lenient).unwrap();
+    assert_eq!(parsed.warnings.len(), 1);
+    assert!(parsed.orgs["Bishopric"].callings[0].member.is_some());
+}
+
+#[test]
+fn lenient_mode_defaults_or_skips_bad_records() {
+    let data = r#"[{"name": "Primary", "callings": [], "children": [
+        {"name": "Valiant 9", "children": [], "callings": [
+            {"position": "Primary Teacher", "memberName": "Smith, John", "activeDate": "2023031", "setApart": true},
+            {"position": "Primary Teacher", "memberName": "Jones, Mary", "activeDate": "20230301"}
+        ]}
+    ]}]"#;
+
+    let parsed = orgs_from_lcr_data(data, ParseMode::Lenient).unwrap();
+    let callings = &parsed.orgs["Primary"].children[0].callings;
+
+    assert_eq!(callings.len(), 1);
+    assert!(!callings[0].member.as_ref().unwrap().set_apart);
+
+    assert_eq!(parsed.warnings.len(), 2);
+    assert_eq!(
+        parsed.warnings[0].location.org_path,
+        vec!["Primary", "Valiant 9"]
+    );
+    assert_eq!(
+        parsed.warnings[0].location.position.as_deref(),
+        Some("Primary Teacher")
+    );
+    assert!(matches!(
+        parsed.warnings[0].kind,
+        ParseWarningKind::InvalidActiveDate(_)
+    ));
+    assert!(matches!(
+        parsed.warnings[1].kind,
+        ParseWarningKind::MissingSetApart
+    ));
+
+    assert!(orgs_from_lcr_data(data, ParseMode::Strict).is_err());
+}
+
+#[test]
+fn errors_point_to_the_offending_record() {
+    let data = r#"[
+        {"name": "Bishopric", "children": [], "callings": []},
+        {"name": "Primary", "callings": [], "children": [
+            {"name": "Valiant 9", "children": [], "callings": [
+                {"position": "Primary Teacher", "memberName": null},
+                {"position": "Primary Teacher", "memberName": "Smith, John", "activeDate": "2023011", "setApart": true}
+            ]}
+        ]}
+    ]"#;
+
+    match orgs_from_lcr_data(data, ParseMode::Strict) {
+        Err(ParseError::InvalidRecord { location, .. }) => {
+            assert_eq!(location.json_path, "[1].children[0].callings[1].activeDate");
+            assert_eq!(location.org_path, vec!["Primary", "Valiant 9"]);
+            assert_eq!(location.position.as_deref(), Some("Primary Teacher"));
+        }
+        other => panic!("expected invalid record, got {:?}", other.map(|_| ())),
+    }
+
+    let data = r#"[{"name": "Primary", "callings": [], "children": [
+        {"name": "Valiant 9", "children": [], "callings": [
+            {"position": "Primary Teacher", "memberName": "Smith, John", "activeDate": "20230101", "setApart": "yes"}
+        ]}
+    ]}]"#;
+
+    // A wrongly typed field is a JSON error in strict mode, so serde's line and column are kept
+    match orgs_from_lcr_data(data, ParseMode::Strict) {
+        Err(ParseError::JsonError { error, location }) => {
+            assert_eq!(error.line(), 3);
+
+            let location = location.unwrap();
+            assert_eq!(location.json_path, "[0].children[0].callings[0].setApart");
+            assert_eq!(location.org_path, vec!["Primary", "Valiant 9"]);
+            assert_eq!(location.position.as_deref(), Some("Primary Teacher"));
+        }
+        other => panic!("expected JSON error, got {:?}", other.map(|_| ())),
+    }
+
+    let parsed = orgs_from_lcr_data(data, ParseMode::Lenient).unwrap();
+    assert_eq!(parsed.warnings.len(), 1);
+
+    let warning = &parsed.warnings[0];
+    assert_eq!(
+        warning.location.json_path,
+        "[0].children[0].callings[0].setApart"
+    );
+    assert_eq!(warning.location.org_path, vec!["Primary", "Valiant 9"]);
+    assert_eq!(
+        warning.location.position.as_deref(),
+        Some("Primary Teacher")
+    );
+    assert!(matches!(
+        warning.kind,
+        ParseWarningKind::InvalidField {
+            field: "setApart",
+            ..
+        }
+    ));
+
+    let member = parsed.orgs["Primary"].children[0].callings[0]
+        .member
+        .as_ref()
+        .unwrap();
+    assert!(!member.set_apart);
+}
+
+#[test]
+fn lenient_mode_skips_callings_with_wrongly_typed_fields() {
+    let data = r#"[{"name": "Primary", "children": [], "callings": [
+        {"position": "Primary President", "memberName": "Smith, John", "activeDate": 20230101, "setApart": true},
+        {"position": null, "memberName": "Jones, Mary", "activeDate": "20230101", "setApart": true},
+        {"position": "Primary Secretary", "memberName": "Lee, Sam", "activeDate": "20230101", "setApart": true, "positionId": "abc"}
+    ]}]"#;
+
+    assert!(orgs_from_lcr_data(data, ParseMode::Strict).is_err());
+
+    let parsed = orgs_from_lcr_data(data, ParseMode::Lenient).unwrap();
+    let callings = &parsed.orgs["Primary"].callings;
+
+    // The secretary is kept, just without a position id
+    assert_eq!(callings.len(), 1);
+    assert_eq!(callings[0].name, "Primary Secretary");
+    assert_eq!(callings[0].position_id, None);
+
+    let fields = parsed
+        .warnings
+        .iter()
+        .map(|w| match &w.kind {
+            ParseWarningKind::InvalidField { field, .. } => *field,
+            other => panic!("unexpected warning {:?}", other),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(fields, vec!["activeDate", "position", "positionId"]);
+    assert_eq!(
+        parsed.warnings[1].location.json_path,
+        "[0].callings[1].position"
+    );
+}